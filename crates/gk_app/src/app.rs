@@ -1,3 +1,4 @@
+use crate::touch::TouchTracker;
 use crate::{Manager, PlatformConfig, Window};
 use gk_sys::window::{GKApp, WindowAttributes, WindowId};
 use gk_sys::Plugin;
@@ -7,6 +8,8 @@ pub struct App {
     pub manager: Manager,
     main_window: Option<WindowId>,
     window_ids: Vec<WindowId>,
+    touch_as_mouse: bool,
+    pub(crate) touches: TouchTracker,
 }
 
 impl App {
@@ -15,6 +18,8 @@ impl App {
             manager: Manager::new(),
             main_window: None,
             window_ids: vec![],
+            touch_as_mouse: false,
+            touches: TouchTracker::default(),
         }
     }
 
@@ -81,6 +86,15 @@ impl App {
     pub fn exit(&mut self) {
         self.manager.exit();
     }
+
+    /// Dispatch mouse events from the first finger's touches
+    pub fn set_touch_as_mouse(&mut self, enable: bool) {
+        self.touch_as_mouse = enable;
+    }
+
+    pub fn touch_as_mouse(&self) -> bool {
+        self.touch_as_mouse
+    }
 }
 
 impl Plugin for App {}
@@ -6,6 +6,7 @@ use gk_sys::{AppBuilder, BuildConfig, GKState};
 pub struct PlatformConfig {
     main_window: Option<WindowAttributes>,
     auto_redraw: bool,
+    touch_as_mouse: bool,
 }
 
 impl Default for PlatformConfig {
@@ -13,6 +14,7 @@ impl Default for PlatformConfig {
         Self {
             main_window: Some(Default::default()),
             auto_redraw: true,
+            touch_as_mouse: false,
         }
     }
 }
@@ -27,11 +29,18 @@ impl PlatformConfig {
         self.main_window = Some(attrs);
         self
     }
+
+    /// Emulate the left mouse button with the first finger's touches
+    pub fn with_touch_as_mouse(mut self, enable: bool) -> Self {
+        self.touch_as_mouse = enable;
+        self
+    }
 }
 
 impl<S: GKState> BuildConfig<S> for PlatformConfig {
     fn apply(&mut self, builder: AppBuilder<S>) -> Result<AppBuilder<S>, String> {
         let mut platform = App::new();
+        platform.set_touch_as_mouse(self.touch_as_mouse);
 
         // Initialize main windows if is not windowless mode
         if let Some(attrs) = self.main_window.take() {
@@ -1,11 +1,105 @@
 use super::window::Window;
-use gk_sys::window::{CursorIcon, GKApp, WindowAttributes, WindowId};
+use crate::touch::dispatch_touch;
+use gk_sys::keyboard::{
+    ImeAction, ImeEvent, KeyCode, KeyModifiers, KeyboardAction, KeyboardEvent, TextEvent,
+};
+use gk_sys::mouse::MouseEvent;
+use gk_sys::touch::TouchEvent;
+use gk_sys::window::{CursorIcon, FileDropEvent, GKApp, WindowAttributes, WindowId};
+use gk_sys::{GKState, System};
 use hashbrown::HashMap;
+use std::collections::VecDeque;
+
+/// Events injected to be dispatched by the runner on the next frame
+#[derive(Debug, Clone)]
+pub(crate) enum InjectedEvent {
+    Keyboard(KeyboardEvent),
+    Mouse(MouseEvent),
+    Text(TextEvent),
+    Ime(ImeEvent),
+    Touch(TouchEvent),
+    FileDrop(FileDropEvent),
+}
 
 #[derive(Default)]
 pub struct Manager {
     pub(crate) windows: HashMap<WindowId, Window>,
     pub(crate) request_exit: bool,
+    pub(crate) modifiers: KeyModifiers,
+    pub(crate) injected: VecDeque<InjectedEvent>,
+}
+
+impl Manager {
+    /// Inject a modifiers change, the state is used by the next injected keyboard events too
+    pub fn inject_modifiers(&mut self, window_id: WindowId, modifiers: KeyModifiers) {
+        self.modifiers = modifiers;
+        self.inject_keyboard(window_id, KeyboardAction::ModifiersChanged);
+    }
+
+    /// Inject a key press
+    pub fn inject_key_pressed(&mut self, window_id: WindowId, key: KeyCode) {
+        self.inject_keyboard(window_id, KeyboardAction::Pressed { key });
+    }
+
+    /// Inject a key release
+    pub fn inject_key_released(&mut self, window_id: WindowId, key: KeyCode) {
+        self.inject_keyboard(window_id, KeyboardAction::Released { key });
+    }
+
+    fn inject_keyboard(&mut self, window_id: WindowId, action: KeyboardAction) {
+        self.injected
+            .push_back(InjectedEvent::Keyboard(KeyboardEvent {
+                window_id,
+                action,
+                modifiers: self.modifiers,
+            }));
+    }
+
+    /// Inject a mouse event
+    pub fn inject_mouse(&mut self, evt: MouseEvent) {
+        self.injected.push_back(InjectedEvent::Mouse(evt));
+    }
+
+    /// Inject typed text
+    pub fn inject_text(&mut self, window_id: WindowId, text: &str) {
+        self.injected.push_back(InjectedEvent::Text(TextEvent {
+            window_id,
+            text: text.to_string(),
+        }));
+    }
+
+    /// Inject an IME event
+    pub fn inject_ime(&mut self, window_id: WindowId, action: ImeAction) {
+        self.injected
+            .push_back(InjectedEvent::Ime(ImeEvent { window_id, action }));
+    }
+
+    /// Inject a touch event, gestures and emulated mouse events are dispatched after it
+    pub fn inject_touch(&mut self, evt: TouchEvent) {
+        self.injected.push_back(InjectedEvent::Touch(evt));
+    }
+
+    /// Inject a file drag and drop event
+    pub fn inject_file_drop(&mut self, evt: FileDropEvent) {
+        self.injected.push_back(InjectedEvent::FileDrop(evt));
+    }
+
+    pub(crate) fn take_injected(&mut self) -> Option<InjectedEvent> {
+        self.injected.pop_front()
+    }
+}
+
+impl InjectedEvent {
+    pub(crate) fn dispatch<S: GKState>(self, sys: &mut System<S>) {
+        match self {
+            InjectedEvent::Keyboard(evt) => sys.event(evt),
+            InjectedEvent::Mouse(evt) => sys.event(evt),
+            InjectedEvent::Text(evt) => sys.event(evt),
+            InjectedEvent::Ime(evt) => sys.event(evt),
+            InjectedEvent::Touch(evt) => dispatch_touch(sys, evt),
+            InjectedEvent::FileDrop(evt) => sys.event(evt),
+        }
+    }
 }
 
 impl GKApp<Window> for Manager {
@@ -25,6 +119,8 @@ impl GKApp<Window> for Manager {
             resizable: attrs.resizable,
             min_size: None,
             max_size: None,
+            ime_allowed: false,
+            ime_position: (0, 0),
         };
         self.windows.insert(id, win);
         Ok(id)
@@ -6,6 +6,7 @@ pub fn runner<S: GKState>(mut app: System<S>) -> Result<(), String> {
 
     loop {
         app.frame_start();
+        dispatch_injected_events(&mut app);
         app.update();
 
         let request_exit = app
@@ -25,3 +26,107 @@ pub fn runner<S: GKState>(mut app: System<S>) -> Result<(), String> {
 
     Ok(())
 }
+
+/// Dispatch the events injected since the last frame
+fn dispatch_injected_events<S: GKState>(app: &mut System<S>) {
+    while let Some(evt) = app
+        .get_mut_plugin::<App>()
+        .and_then(|platform| platform.manager.take_injected())
+    {
+        evt.dispatch(app);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::keyboard::Keyboard;
+    use gk_sys::event::UpdateEvent;
+    use gk_sys::keyboard::{ImeAction, ImeEvent, KeyCode, KeyModifiers, KeyboardEvent, TextEvent};
+    use gk_sys::prelude::*;
+    use gk_sys::AppState;
+
+    #[derive(AppState, Default)]
+    struct State {
+        frame: usize,
+        text: String,
+        ime: Vec<ImeAction>,
+        modifiers: Option<KeyModifiers>,
+    }
+
+    #[test]
+    fn test_injected_events() {
+        let shift = KeyModifiers {
+            shift: true,
+            ..Default::default()
+        };
+
+        AppBuilder::init_with(|| Ok(State::default()))
+            .add_config(App::config().with_windowless())
+            .unwrap()
+            .add_config(Keyboard::config())
+            .unwrap()
+            .on(|evt: &TextEvent, state: &mut State| state.text.push_str(&evt.text))
+            .on(|evt: &ImeEvent, state: &mut State| state.ime.push(evt.action.clone()))
+            .on(|evt: &KeyboardEvent, state: &mut State| state.modifiers = Some(evt.modifiers))
+            .on(
+                move |_: &UpdateEvent,
+                      app: &mut App,
+                      keyboard: &mut Keyboard,
+                      state: &mut State| {
+                    state.frame += 1;
+                    let id = 0.into();
+                    match state.frame {
+                        1 => {
+                            app.manager.inject_modifiers(id, shift);
+                            app.manager.inject_text(id, "H");
+                            app.manager.inject_text(id, "i");
+                            app.manager.inject_ime(id, ImeAction::Enabled);
+                            app.manager.inject_ime(
+                                id,
+                                ImeAction::Preedit {
+                                    text: "ni".to_string(),
+                                    cursor: Some((2, 2)),
+                                },
+                            );
+                            app.manager.inject_ime(
+                                id,
+                                ImeAction::Commit {
+                                    text: "你".to_string(),
+                                },
+                            );
+                        }
+                        2 => {
+                            assert_eq!(state.text, "Hi");
+                            assert_eq!(
+                                state.ime,
+                                vec![
+                                    ImeAction::Enabled,
+                                    ImeAction::Preedit {
+                                        text: "ni".to_string(),
+                                        cursor: Some((2, 2)),
+                                    },
+                                    ImeAction::Commit {
+                                        text: "你".to_string(),
+                                    },
+                                ]
+                            );
+
+                            // the modifiers are tracked without any key pressed
+                            assert_eq!(state.modifiers, Some(shift));
+                            assert_eq!(keyboard.modifiers(), shift);
+
+                            app.manager.inject_key_pressed(id, KeyCode::H);
+                        }
+                        _ => {
+                            assert!(keyboard.is_down(KeyCode::H));
+                            assert_eq!(state.modifiers, Some(shift));
+                            app.exit();
+                        }
+                    }
+                },
+            )
+            .build()
+            .unwrap();
+    }
+}
@@ -12,6 +12,8 @@ pub struct Window {
     pub(crate) resizable: bool,
     pub(crate) min_size: Option<(u32, u32)>,
     pub(crate) max_size: Option<(u32, u32)>,
+    pub(crate) ime_allowed: bool,
+    pub(crate) ime_position: (i32, i32),
 }
 
 impl Default for Window {
@@ -25,6 +27,8 @@ impl Default for Window {
             resizable: false,
             min_size: None,
             max_size: None,
+            ime_allowed: false,
+            ime_position: (0, 0),
         }
     }
 }
@@ -167,4 +171,16 @@ impl GKWindow for Window {
     }
 
     fn request_redraw(&mut self) {}
+
+    fn set_ime_allowed(&mut self, allowed: bool) {
+        self.ime_allowed = allowed;
+    }
+
+    fn ime_allowed(&self) -> bool {
+        self.ime_allowed
+    }
+
+    fn set_ime_position(&mut self, x: i32, y: i32) {
+        self.ime_position = (x, y);
+    }
 }
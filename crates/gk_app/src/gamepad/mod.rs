@@ -0,0 +1,242 @@
+#[cfg(all(feature = "gilrs", not(target_arch = "wasm32")))]
+mod native;
+mod virtual_pad;
+
+#[cfg(all(feature = "gilrs", not(target_arch = "wasm32")))]
+pub use native::*;
+pub use virtual_pad::*;
+
+use gk_sys::event;
+use gk_sys::gamepad::{GamepadAction, GamepadAxis, GamepadButton, GamepadEvent, GamepadId};
+use gk_sys::{AppBuilder, BuildConfig, EventQueue, GKState, Plugin};
+use hashbrown::{HashMap, HashSet};
+
+/// A source of gamepad events, like the native backend or a virtual pad
+pub trait GamepadSource {
+    /// Returns the next pending event
+    fn next_event(&mut self) -> Option<GamepadEvent>;
+}
+
+/// State of a connected gamepad
+#[derive(Debug, Default, Clone)]
+pub struct Gamepad {
+    name: String,
+    pressed: HashSet<GamepadButton>,
+    down: HashSet<GamepadButton>,
+    released: HashSet<GamepadButton>,
+    axes: HashMap<GamepadAxis, f32>,
+}
+
+impl Gamepad {
+    /// Name given by the device
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns true if the button was pressed this frame
+    #[inline]
+    pub fn was_pressed(&self, button: GamepadButton) -> bool {
+        self.pressed.contains(&button)
+    }
+
+    /// Returns true while the button is down
+    #[inline]
+    pub fn is_down(&self, button: GamepadButton) -> bool {
+        self.down.contains(&button)
+    }
+
+    /// Returns true if the button was released this frame
+    #[inline]
+    pub fn was_released(&self, button: GamepadButton) -> bool {
+        self.released.contains(&button)
+    }
+
+    /// Last value of the axis, between -1.0 and 1.0
+    #[inline]
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes.get(&axis).copied().unwrap_or(0.0)
+    }
+}
+
+/// Keeps track of the connected gamepads and their state
+#[derive(Default)]
+pub struct Gamepads {
+    sources: Vec<Box<dyn GamepadSource>>,
+    pads: HashMap<GamepadId, Gamepad>,
+    ids: Vec<GamepadId>,
+}
+
+impl Gamepads {
+    pub fn config() -> GamepadsConfig {
+        GamepadsConfig::default()
+    }
+
+    /// Ids of the connected gamepads, in connection order
+    pub fn ids(&self) -> &[GamepadId] {
+        &self.ids
+    }
+
+    /// State of the gamepad if it's connected
+    pub fn gamepad(&self, id: GamepadId) -> Option<&Gamepad> {
+        self.pads.get(&id)
+    }
+
+    /// Returns true if the gamepad is connected
+    pub fn is_connected(&self, id: GamepadId) -> bool {
+        self.pads.contains_key(&id)
+    }
+
+    /// Read the sources and queue the events received
+    pub(crate) fn update<S: GKState + 'static>(&mut self, events: &mut EventQueue<S>) {
+        let mut received = vec![];
+        self.sources.iter_mut().for_each(|source| {
+            while let Some(evt) = source.next_event() {
+                received.push(evt);
+            }
+        });
+
+        received.into_iter().for_each(|evt| {
+            self.process(&evt);
+            events.queue(evt);
+        });
+    }
+
+    pub(crate) fn process(&mut self, evt: &GamepadEvent) {
+        match &evt.action {
+            GamepadAction::Connected { name } => {
+                if !self.pads.contains_key(&evt.id) {
+                    self.ids.push(evt.id);
+                }
+
+                self.pads.insert(
+                    evt.id,
+                    Gamepad {
+                        name: name.clone(),
+                        ..Default::default()
+                    },
+                );
+            }
+            GamepadAction::Disconnected => {
+                self.pads.remove(&evt.id);
+                self.ids.retain(|id| *id != evt.id);
+            }
+            GamepadAction::ButtonPressed { button } => {
+                if let Some(pad) = self.pads.get_mut(&evt.id) {
+                    if pad.down.insert(*button) {
+                        pad.pressed.insert(*button);
+                    }
+                }
+            }
+            GamepadAction::ButtonReleased { button } => {
+                if let Some(pad) = self.pads.get_mut(&evt.id) {
+                    pad.down.remove(button);
+                    pad.released.insert(*button);
+                }
+            }
+            GamepadAction::AxisChanged { axis, value } => {
+                if let Some(pad) = self.pads.get_mut(&evt.id) {
+                    pad.axes.insert(*axis, *value);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.pads.values_mut().for_each(|pad| {
+            pad.pressed.clear();
+            pad.released.clear();
+        });
+    }
+}
+
+impl Plugin for Gamepads {}
+
+#[derive(Default)]
+pub struct GamepadsConfig {
+    sources: Vec<Box<dyn GamepadSource>>,
+}
+
+impl GamepadsConfig {
+    /// Read events from `source`, the native backend is not used if any source is set
+    pub fn with_source<T: GamepadSource + 'static>(mut self, source: T) -> Self {
+        self.sources.push(Box::new(source));
+        self
+    }
+}
+
+impl<S: GKState + 'static> BuildConfig<S> for GamepadsConfig {
+    fn apply(&mut self, builder: AppBuilder<S>) -> Result<AppBuilder<S>, String> {
+        let mut sources = std::mem::take(&mut self.sources);
+        if sources.is_empty() {
+            sources.extend(native_source());
+        }
+
+        let gamepads = Gamepads {
+            sources,
+            ..Default::default()
+        };
+
+        let builder = builder
+            .on(
+                |_: &event::FrameStartEvent,
+                 gamepads: &mut Gamepads,
+                 events: &mut EventQueue<S>| { gamepads.update(events) },
+            )
+            .on(|_: &event::FrameEndEvent, gamepads: &mut Gamepads| gamepads.reset());
+        Ok(builder.add_plugin(gamepads))
+    }
+}
+
+#[cfg(all(feature = "gilrs", not(target_arch = "wasm32")))]
+fn native_source() -> Option<Box<dyn GamepadSource>> {
+    match GilrsSource::new() {
+        Ok(source) => Some(Box::new(source)),
+        Err(err) => {
+            log::warn!("Gamepads cannot be initialized: {}", err);
+            None
+        }
+    }
+}
+
+#[cfg(not(all(feature = "gilrs", not(target_arch = "wasm32"))))]
+fn native_source() -> Option<Box<dyn GamepadSource>> {
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gamepad_state() {
+        let pad = VirtualGamepad::new(0.into(), "Virtual Pad");
+        let mut gamepads = Gamepads::default();
+        gamepads.sources.push(Box::new(pad.clone()));
+
+        let mut events = EventQueue::<()>::default();
+        pad.connect();
+        pad.press(GamepadButton::South);
+        pad.set_axis(GamepadAxis::LeftStickX, 0.5);
+        gamepads.update(&mut events);
+
+        let id = 0.into();
+        assert_eq!(gamepads.ids(), &[id]);
+        let state = gamepads.gamepad(id).unwrap();
+        assert_eq!(state.name(), "Virtual Pad");
+        assert!(state.was_pressed(GamepadButton::South));
+        assert!(state.is_down(GamepadButton::South));
+        assert_eq!(state.axis(GamepadAxis::LeftStickX), 0.5);
+
+        gamepads.reset();
+        pad.release(GamepadButton::South);
+        gamepads.update(&mut events);
+        let state = gamepads.gamepad(id).unwrap();
+        assert!(!state.was_pressed(GamepadButton::South));
+        assert!(state.was_released(GamepadButton::South));
+
+        pad.disconnect();
+        gamepads.update(&mut events);
+        assert!(!gamepads.is_connected(id));
+        assert!(gamepads.ids().is_empty());
+    }
+}
@@ -0,0 +1,84 @@
+use super::GamepadSource;
+use gilrs::{Axis, Button, EventType, Gilrs};
+use gk_sys::gamepad::{GamepadAction, GamepadAxis, GamepadButton, GamepadEvent, GamepadId};
+
+/// Native gamepads using `gilrs`
+pub struct GilrsSource {
+    raw: Gilrs,
+}
+
+impl GilrsSource {
+    pub fn new() -> Result<Self, String> {
+        let raw = Gilrs::new().map_err(|err| err.to_string())?;
+        Ok(Self { raw })
+    }
+}
+
+impl GamepadSource for GilrsSource {
+    fn next_event(&mut self) -> Option<GamepadEvent> {
+        while let Some(evt) = self.raw.next_event() {
+            let id = GamepadId::from(usize::from(evt.id) as u64);
+            let action = match evt.event {
+                EventType::Connected => GamepadAction::Connected {
+                    name: self.raw.gamepad(evt.id).name().to_string(),
+                },
+                EventType::Disconnected => GamepadAction::Disconnected,
+                EventType::ButtonPressed(btn, _) => GamepadAction::ButtonPressed {
+                    button: button_id(btn),
+                },
+                EventType::ButtonReleased(btn, _) => GamepadAction::ButtonReleased {
+                    button: button_id(btn),
+                },
+                EventType::AxisChanged(axis, value, _) => GamepadAction::AxisChanged {
+                    axis: axis_id(axis),
+                    value,
+                },
+                // repeated, analog buttons and dropped events are ignored
+                _ => continue,
+            };
+
+            return Some(GamepadEvent { id, action });
+        }
+
+        None
+    }
+}
+
+fn button_id(btn: Button) -> GamepadButton {
+    match btn {
+        Button::South => GamepadButton::South,
+        Button::East => GamepadButton::East,
+        Button::North => GamepadButton::North,
+        Button::West => GamepadButton::West,
+        Button::C => GamepadButton::C,
+        Button::Z => GamepadButton::Z,
+        Button::LeftTrigger => GamepadButton::LeftTrigger,
+        Button::LeftTrigger2 => GamepadButton::LeftTrigger2,
+        Button::RightTrigger => GamepadButton::RightTrigger,
+        Button::RightTrigger2 => GamepadButton::RightTrigger2,
+        Button::Select => GamepadButton::Select,
+        Button::Start => GamepadButton::Start,
+        Button::Mode => GamepadButton::Mode,
+        Button::LeftThumb => GamepadButton::LeftThumb,
+        Button::RightThumb => GamepadButton::RightThumb,
+        Button::DPadUp => GamepadButton::DPadUp,
+        Button::DPadDown => GamepadButton::DPadDown,
+        Button::DPadLeft => GamepadButton::DPadLeft,
+        Button::DPadRight => GamepadButton::DPadRight,
+        Button::Unknown => GamepadButton::Unknown,
+    }
+}
+
+fn axis_id(axis: Axis) -> GamepadAxis {
+    match axis {
+        Axis::LeftStickX => GamepadAxis::LeftStickX,
+        Axis::LeftStickY => GamepadAxis::LeftStickY,
+        Axis::LeftZ => GamepadAxis::LeftZ,
+        Axis::RightStickX => GamepadAxis::RightStickX,
+        Axis::RightStickY => GamepadAxis::RightStickY,
+        Axis::RightZ => GamepadAxis::RightZ,
+        Axis::DPadX => GamepadAxis::DPadX,
+        Axis::DPadY => GamepadAxis::DPadY,
+        Axis::Unknown => GamepadAxis::Unknown,
+    }
+}
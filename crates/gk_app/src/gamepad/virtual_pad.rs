@@ -0,0 +1,71 @@
+use super::GamepadSource;
+use gk_sys::gamepad::{GamepadAction, GamepadAxis, GamepadButton, GamepadEvent, GamepadId};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// A scripted gamepad, clones share the same event queue so it can be driven
+/// from outside once it's set as source of `Gamepads`
+#[derive(Debug, Clone)]
+pub struct VirtualGamepad {
+    id: GamepadId,
+    name: String,
+    queue: Arc<Mutex<VecDeque<GamepadEvent>>>,
+}
+
+impl VirtualGamepad {
+    pub fn new(id: GamepadId, name: &str) -> Self {
+        Self {
+            id,
+            name: name.to_string(),
+            queue: Default::default(),
+        }
+    }
+
+    pub fn id(&self) -> GamepadId {
+        self.id
+    }
+
+    pub fn connect(&self) {
+        self.send(GamepadAction::Connected {
+            name: self.name.clone(),
+        });
+    }
+
+    pub fn disconnect(&self) {
+        self.send(GamepadAction::Disconnected);
+    }
+
+    pub fn press(&self, button: GamepadButton) {
+        self.send(GamepadAction::ButtonPressed { button });
+    }
+
+    pub fn release(&self, button: GamepadButton) {
+        self.send(GamepadAction::ButtonReleased { button });
+    }
+
+    pub fn set_axis(&self, axis: GamepadAxis, value: f32) {
+        self.send(GamepadAction::AxisChanged {
+            axis,
+            value: value.clamp(-1.0, 1.0),
+        });
+    }
+
+    /// Send a custom action
+    pub fn send(&self, action: GamepadAction) {
+        if let Ok(mut queue) = self.queue.lock() {
+            queue.push_back(GamepadEvent {
+                id: self.id,
+                action,
+            });
+        }
+    }
+}
+
+impl GamepadSource for VirtualGamepad {
+    fn next_event(&mut self) -> Option<GamepadEvent> {
+        self.queue
+            .lock()
+            .ok()
+            .and_then(|mut queue| queue.pop_front())
+    }
+}
@@ -0,0 +1,156 @@
+use gk_sys::event;
+use gk_sys::keyboard::{KeyCode, KeyModifiers, KeyboardAction, KeyboardEvent};
+use gk_sys::window::{WindowAction, WindowEvent};
+use gk_sys::{AppBuilder, BuildConfig, GKState, Plugin};
+use hashbrown::HashSet;
+
+/// Keeps track of the keyboard's keys state
+#[derive(Debug, Default, Clone)]
+pub struct Keyboard {
+    pressed: HashSet<KeyCode>,
+    down: HashSet<KeyCode>,
+    released: HashSet<KeyCode>,
+    modifiers: KeyModifiers,
+}
+
+impl Keyboard {
+    pub fn config() -> KeyboardConfig {
+        KeyboardConfig
+    }
+
+    pub(crate) fn process(&mut self, evt: &KeyboardEvent) {
+        self.modifiers = evt.modifiers;
+
+        match evt.action {
+            KeyboardAction::Pressed { key } => {
+                // key repeat sends pressed events while the key is down
+                if self.down.insert(key) {
+                    self.pressed.insert(key);
+                }
+            }
+            KeyboardAction::Released { key } => {
+                self.down.remove(&key);
+                self.released.insert(key);
+            }
+            KeyboardAction::ModifiersChanged => {}
+        }
+    }
+
+    /// The window doesn't receive the release events without focus,
+    /// so the keys down are released
+    pub(crate) fn release_all(&mut self) {
+        self.released.extend(self.down.drain());
+        self.modifiers = KeyModifiers::default();
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.pressed.clear();
+        self.released.clear();
+    }
+
+    /// Returns true if the key was pressed this frame
+    #[inline]
+    pub fn was_pressed(&self, key: KeyCode) -> bool {
+        self.pressed.contains(&key)
+    }
+
+    /// Returns true while the key is down
+    #[inline]
+    pub fn is_down(&self, key: KeyCode) -> bool {
+        self.down.contains(&key)
+    }
+
+    /// Returns true if the key was released this frame
+    #[inline]
+    pub fn was_released(&self, key: KeyCode) -> bool {
+        self.released.contains(&key)
+    }
+
+    /// Modifiers state of the last keyboard event
+    #[inline]
+    pub fn modifiers(&self) -> KeyModifiers {
+        self.modifiers
+    }
+
+    /// Keys pressed this frame
+    pub fn pressed(&self) -> impl Iterator<Item = &KeyCode> {
+        self.pressed.iter()
+    }
+
+    /// Keys that are down
+    pub fn down(&self) -> impl Iterator<Item = &KeyCode> {
+        self.down.iter()
+    }
+
+    /// Keys released this frame
+    pub fn released(&self) -> impl Iterator<Item = &KeyCode> {
+        self.released.iter()
+    }
+}
+
+impl Plugin for Keyboard {}
+
+#[derive(Debug, Default, Copy, Clone)]
+pub struct KeyboardConfig;
+
+impl<S: GKState + 'static> BuildConfig<S> for KeyboardConfig {
+    fn apply(&mut self, builder: AppBuilder<S>) -> Result<AppBuilder<S>, String> {
+        let builder = builder
+            .on(|evt: &KeyboardEvent, keyboard: &mut Keyboard| keyboard.process(evt))
+            .on(|evt: &WindowEvent, keyboard: &mut Keyboard| {
+                if evt.action == WindowAction::FocusLost {
+                    keyboard.release_all();
+                }
+            })
+            .on(|_: &event::FrameEndEvent, keyboard: &mut Keyboard| keyboard.reset());
+        Ok(builder.add_plugin(Keyboard::default()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gk_sys::window::WindowId;
+
+    fn evt(action: KeyboardAction) -> KeyboardEvent {
+        KeyboardEvent {
+            window_id: WindowId::from(0),
+            action,
+            modifiers: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_key_state() {
+        let mut keyboard = Keyboard::default();
+        keyboard.process(&evt(KeyboardAction::Pressed { key: KeyCode::A }));
+        assert!(keyboard.was_pressed(KeyCode::A));
+        assert!(keyboard.is_down(KeyCode::A));
+
+        keyboard.reset();
+        keyboard.process(&evt(KeyboardAction::Pressed { key: KeyCode::A }));
+        assert!(!keyboard.was_pressed(KeyCode::A));
+        assert!(keyboard.is_down(KeyCode::A));
+
+        keyboard.process(&evt(KeyboardAction::Released { key: KeyCode::A }));
+        assert!(keyboard.was_released(KeyCode::A));
+        assert!(!keyboard.is_down(KeyCode::A));
+
+        keyboard.reset();
+        assert!(!keyboard.was_released(KeyCode::A));
+    }
+
+    #[test]
+    fn test_focus_lost() {
+        let mut keyboard = Keyboard::default();
+        keyboard.process(&evt(KeyboardAction::Pressed { key: KeyCode::A }));
+        keyboard.reset();
+
+        keyboard.release_all();
+        assert!(!keyboard.is_down(KeyCode::A));
+        assert!(keyboard.was_released(KeyCode::A));
+
+        keyboard.reset();
+        assert!(!keyboard.was_released(KeyCode::A));
+    }
+}
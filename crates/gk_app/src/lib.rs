@@ -12,6 +12,13 @@ pub use crate::winit::*;
 
 mod app;
 mod config;
+mod gamepad;
+mod keyboard;
+mod mouse;
+mod touch;
 
 pub use app::*;
 pub use config::*;
+pub use gamepad::*;
+pub use keyboard::*;
+pub use mouse::*;
@@ -0,0 +1,196 @@
+use gk_sys::event;
+use gk_sys::mouse::{MouseAction, MouseButton, MouseEvent};
+use gk_sys::window::{WindowAction, WindowEvent, WindowId};
+use gk_sys::{AppBuilder, BuildConfig, GKState, Plugin};
+use hashbrown::HashSet;
+
+/// Keeps track of the mouse's buttons, position and wheel
+#[derive(Debug, Default, Clone)]
+pub struct Mouse {
+    pressed: HashSet<MouseButton>,
+    down: HashSet<MouseButton>,
+    released: HashSet<MouseButton>,
+    position: (f32, f32),
+    motion_delta: (f32, f32),
+    wheel_delta: (f32, f32),
+    window: Option<WindowId>,
+}
+
+impl Mouse {
+    pub fn config() -> MouseConfig {
+        MouseConfig
+    }
+
+    pub(crate) fn process(&mut self, evt: &MouseEvent) {
+        self.position = (evt.x, evt.y);
+
+        match evt.action {
+            MouseAction::Wheel { delta_x, delta_y } => {
+                self.wheel_delta.0 += delta_x;
+                self.wheel_delta.1 += delta_y;
+            }
+            MouseAction::ButtonPressed { button } => {
+                if self.down.insert(button) {
+                    self.pressed.insert(button);
+                }
+            }
+            MouseAction::ButtonReleased { button } => {
+                self.down.remove(&button);
+                self.released.insert(button);
+            }
+            MouseAction::Move {
+                relative_x,
+                relative_y,
+            } => {
+                self.motion_delta.0 += relative_x;
+                self.motion_delta.1 += relative_y;
+            }
+            MouseAction::Enter => self.window = Some(evt.window_id),
+            MouseAction::Left => self.window = None,
+        }
+    }
+
+    /// The window doesn't receive the release events without focus,
+    /// so the buttons down are released
+    pub(crate) fn release_all(&mut self) {
+        self.released.extend(self.down.drain());
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.pressed.clear();
+        self.released.clear();
+        self.motion_delta = (0.0, 0.0);
+        self.wheel_delta = (0.0, 0.0);
+    }
+
+    /// Returns true if the button was pressed this frame
+    #[inline]
+    pub fn was_pressed(&self, button: MouseButton) -> bool {
+        self.pressed.contains(&button)
+    }
+
+    /// Returns true while the button is down
+    #[inline]
+    pub fn is_down(&self, button: MouseButton) -> bool {
+        self.down.contains(&button)
+    }
+
+    /// Returns true if the button was released this frame
+    #[inline]
+    pub fn was_released(&self, button: MouseButton) -> bool {
+        self.released.contains(&button)
+    }
+
+    /// Last cursor position
+    #[inline]
+    pub fn position(&self) -> (f32, f32) {
+        self.position
+    }
+
+    /// Cursor movement during this frame
+    #[inline]
+    pub fn motion_delta(&self) -> (f32, f32) {
+        self.motion_delta
+    }
+
+    /// Wheel movement during this frame
+    #[inline]
+    pub fn wheel_delta(&self) -> (f32, f32) {
+        self.wheel_delta
+    }
+
+    /// Window where the cursor is, if any
+    #[inline]
+    pub fn window(&self) -> Option<WindowId> {
+        self.window
+    }
+}
+
+impl Plugin for Mouse {}
+
+#[derive(Debug, Default, Copy, Clone)]
+pub struct MouseConfig;
+
+impl<S: GKState + 'static> BuildConfig<S> for MouseConfig {
+    fn apply(&mut self, builder: AppBuilder<S>) -> Result<AppBuilder<S>, String> {
+        let builder = builder
+            .on(|evt: &MouseEvent, mouse: &mut Mouse| mouse.process(evt))
+            .on(|evt: &WindowEvent, mouse: &mut Mouse| {
+                if evt.action == WindowAction::FocusLost {
+                    mouse.release_all();
+                }
+            })
+            .on(|_: &event::FrameEndEvent, mouse: &mut Mouse| mouse.reset());
+        Ok(builder.add_plugin(Mouse::default()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn evt(action: MouseAction) -> MouseEvent {
+        MouseEvent {
+            window_id: WindowId::from(0),
+            action,
+            x: 10.0,
+            y: 20.0,
+        }
+    }
+
+    #[test]
+    fn test_button_state() {
+        let mut mouse = Mouse::default();
+        let left = MouseButton::Left;
+        mouse.process(&evt(MouseAction::ButtonPressed { button: left }));
+        assert!(mouse.was_pressed(left));
+        assert!(mouse.is_down(left));
+        assert_eq!(mouse.position(), (10.0, 20.0));
+
+        mouse.reset();
+        assert!(!mouse.was_pressed(left));
+        assert!(mouse.is_down(left));
+
+        mouse.process(&evt(MouseAction::ButtonReleased { button: left }));
+        assert!(mouse.was_released(left));
+        assert!(!mouse.is_down(left));
+
+        mouse.reset();
+        assert!(!mouse.was_released(left));
+    }
+
+    #[test]
+    fn test_frame_deltas() {
+        let mut mouse = Mouse::default();
+        mouse.process(&evt(MouseAction::Move {
+            relative_x: 1.0,
+            relative_y: 2.0,
+        }));
+        mouse.process(&evt(MouseAction::Move {
+            relative_x: 3.0,
+            relative_y: 4.0,
+        }));
+        mouse.process(&evt(MouseAction::Wheel {
+            delta_x: 0.0,
+            delta_y: -1.0,
+        }));
+        assert_eq!(mouse.motion_delta(), (4.0, 6.0));
+        assert_eq!(mouse.wheel_delta(), (0.0, -1.0));
+
+        mouse.reset();
+        assert_eq!(mouse.motion_delta(), (0.0, 0.0));
+        assert_eq!(mouse.wheel_delta(), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_focus_lost() {
+        let mut mouse = Mouse::default();
+        let right = MouseButton::Right;
+        mouse.process(&evt(MouseAction::ButtonPressed { button: right }));
+        mouse.reset();
+
+        mouse.release_all();
+        assert!(!mouse.is_down(right));
+        assert!(mouse.was_released(right));
+    }
+}
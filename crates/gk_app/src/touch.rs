@@ -0,0 +1,202 @@
+use crate::App;
+use gk_sys::mouse::{MouseAction, MouseButton, MouseEvent};
+use gk_sys::touch::{GestureAction, GestureEvent, TouchEvent, TouchPhase};
+use gk_sys::{GKState, System};
+
+/// Keeps the active touches to detect two fingers gestures and emulate the mouse
+#[derive(Debug, Default, Clone)]
+pub(crate) struct TouchTracker {
+    touches: Vec<(u64, (f32, f32))>,
+    primary: Option<(u64, (f32, f32))>,
+}
+
+impl TouchTracker {
+    /// Updates the touches returning the pinch and rotate gestures if two fingers are used
+    pub(crate) fn gestures(&mut self, evt: &TouchEvent) -> Vec<GestureEvent> {
+        let old = self.pair();
+        match evt.phase {
+            TouchPhase::Started => self.touches.push((evt.id, (evt.x, evt.y))),
+            TouchPhase::Moved => {
+                if let Some((_, pos)) = self.touches.iter_mut().find(|(id, _)| *id == evt.id) {
+                    *pos = (evt.x, evt.y);
+                }
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                self.touches.retain(|(id, _)| *id != evt.id);
+            }
+        }
+        let new = self.pair();
+
+        let (phase, delta_scale, delta_angle) = match (old, new) {
+            (None, Some(_)) => (TouchPhase::Started, 0.0, 0.0),
+            (Some(_), None) => (evt.phase, 0.0, 0.0),
+            (Some(old), Some(new)) if evt.phase == TouchPhase::Moved => {
+                let scale = if old.0 > 0.0 {
+                    new.0 / old.0 - 1.0
+                } else {
+                    0.0
+                };
+                // screen's y axis goes down, negate it to keep counterclockwise as positive
+                let mut angle = (old.1 - new.1).to_degrees();
+                if angle > 180.0 {
+                    angle -= 360.0;
+                } else if angle < -180.0 {
+                    angle += 360.0;
+                }
+                (TouchPhase::Moved, scale, angle)
+            }
+            _ => return vec![],
+        };
+
+        let window_id = evt.window_id;
+        vec![
+            GestureEvent {
+                window_id,
+                action: GestureAction::Pinch {
+                    delta: delta_scale,
+                    phase,
+                },
+            },
+            GestureEvent {
+                window_id,
+                action: GestureAction::Rotate {
+                    delta: delta_angle,
+                    phase,
+                },
+            },
+        ]
+    }
+
+    /// Distance and angle between the first two fingers
+    fn pair(&self) -> Option<(f32, f32)> {
+        match self.touches.as_slice() {
+            [(_, a), (_, b), ..] => {
+                let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+                Some(((dx * dx + dy * dy).sqrt(), dy.atan2(dx)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Translates the first finger's touches to left mouse button events
+    pub(crate) fn emulated_mouse(&mut self, evt: &TouchEvent) -> Option<MouseEvent> {
+        let pos = (evt.x, evt.y);
+        let action = match (evt.phase, self.primary) {
+            (TouchPhase::Started, None) => {
+                self.primary = Some((evt.id, pos));
+                MouseAction::ButtonPressed {
+                    button: MouseButton::Left,
+                }
+            }
+            (TouchPhase::Moved, Some((id, old))) if id == evt.id => {
+                self.primary = Some((id, pos));
+                MouseAction::Move {
+                    relative_x: pos.0 - old.0,
+                    relative_y: pos.1 - old.1,
+                }
+            }
+            (TouchPhase::Ended | TouchPhase::Cancelled, Some((id, _))) if id == evt.id => {
+                self.primary = None;
+                MouseAction::ButtonReleased {
+                    button: MouseButton::Left,
+                }
+            }
+            _ => return None,
+        };
+
+        Some(MouseEvent {
+            window_id: evt.window_id,
+            action,
+            x: evt.x,
+            y: evt.y,
+        })
+    }
+}
+
+/// Dispatch the touch event followed by the gestures and the emulated mouse events
+pub(crate) fn dispatch_touch<S: GKState>(sys: &mut System<S>, evt: TouchEvent) {
+    let (gestures, mouse) = match sys.get_mut_plugin::<App>() {
+        Some(app) => {
+            let gestures = app.touches.gestures(&evt);
+            let mouse = if app.touch_as_mouse() {
+                app.touches.emulated_mouse(&evt)
+            } else {
+                None
+            };
+            (gestures, mouse)
+        }
+        None => (vec![], None),
+    };
+
+    sys.event(evt);
+    gestures.into_iter().for_each(|gesture| sys.event(gesture));
+    if let Some(mouse) = mouse {
+        sys.event(mouse);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn touch(id: u64, phase: TouchPhase, x: f32, y: f32) -> TouchEvent {
+        TouchEvent {
+            window_id: 0.into(),
+            id,
+            phase,
+            x,
+            y,
+            force: None,
+        }
+    }
+
+    #[test]
+    fn test_pinch_gesture() {
+        let mut tracker = TouchTracker::default();
+        assert!(tracker
+            .gestures(&touch(0, TouchPhase::Started, 0.0, 0.0))
+            .is_empty());
+
+        let started = tracker.gestures(&touch(1, TouchPhase::Started, 10.0, 0.0));
+        assert_eq!(started.len(), 2);
+
+        let moved = tracker.gestures(&touch(1, TouchPhase::Moved, 20.0, 0.0));
+        match moved[0].action {
+            GestureAction::Pinch { delta, phase } => {
+                assert_eq!(phase, TouchPhase::Moved);
+                assert!((delta - 1.0).abs() < 0.001);
+            }
+            _ => panic!("Expected a pinch gesture"),
+        }
+
+        let ended = tracker.gestures(&touch(0, TouchPhase::Ended, 0.0, 0.0));
+        assert!(matches!(
+            ended[0].action,
+            GestureAction::Pinch {
+                phase: TouchPhase::Ended,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_emulated_mouse() {
+        let mut tracker = TouchTracker::default();
+        let pressed = tracker.emulated_mouse(&touch(3, TouchPhase::Started, 5.0, 5.0));
+        assert!(matches!(
+            pressed.map(|evt| evt.action),
+            Some(MouseAction::ButtonPressed { .. })
+        ));
+
+        // only the first finger moves the mouse
+        assert!(tracker
+            .emulated_mouse(&touch(4, TouchPhase::Started, 1.0, 1.0))
+            .is_none());
+
+        let released = tracker.emulated_mouse(&touch(3, TouchPhase::Ended, 5.0, 5.0));
+        assert!(matches!(
+            released.map(|evt| evt.action),
+            Some(MouseAction::ButtonReleased { .. })
+        ));
+    }
+}
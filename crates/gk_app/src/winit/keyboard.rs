@@ -1,21 +1,67 @@
-use gk_sys::keyboard::{KeyCode, KeyboardAction, KeyboardEvent};
+use gk_sys::keyboard::{
+    ImeAction, ImeEvent, KeyCode, KeyModifiers, KeyboardAction, KeyboardEvent, TextEvent,
+};
 use gk_sys::window::WindowId;
-use winit::event::{ElementState, KeyboardInput as WKeyboardInput, VirtualKeyCode};
+use winit::event::{
+    ElementState, Ime, KeyboardInput as WKeyboardInput, ModifiersState, VirtualKeyCode,
+};
 
-pub(crate) fn process(window_id: WindowId, input: WKeyboardInput) -> KeyboardEvent {
+pub(crate) fn process(
+    window_id: WindowId,
+    input: WKeyboardInput,
+    modifiers: KeyModifiers,
+) -> KeyboardEvent {
     let key = key_id(input.virtual_keycode.as_ref());
     match input.state {
         ElementState::Pressed => KeyboardEvent {
             window_id,
             action: KeyboardAction::Pressed { key },
+            modifiers,
         },
         ElementState::Released => KeyboardEvent {
             window_id,
             action: KeyboardAction::Released { key },
+            modifiers,
         },
     }
 }
 
+pub(crate) fn process_modifiers(window_id: WindowId, state: ModifiersState) -> KeyboardEvent {
+    KeyboardEvent {
+        window_id,
+        action: KeyboardAction::ModifiersChanged,
+        modifiers: KeyModifiers {
+            shift: state.shift(),
+            ctrl: state.ctrl(),
+            alt: state.alt(),
+            logo: state.logo(),
+        },
+    }
+}
+
+pub(crate) fn process_text(window_id: WindowId, c: char) -> Option<TextEvent> {
+    // control characters (backspace, delete, etc...) are handled as keyboard events
+    if c.is_control() {
+        return None;
+    }
+
+    Some(TextEvent {
+        window_id,
+        text: c.to_string(),
+    })
+}
+
+pub(crate) fn process_ime(window_id: WindowId, ime: Ime) -> ImeEvent {
+    let action = match ime {
+        Ime::Enabled => ImeAction::Enabled,
+        Ime::Preedit(text, cursor) => ImeAction::Preedit { text, cursor },
+        Ime::Commit(text) => ImeAction::Commit { text },
+        Ime::Disabled => ImeAction::Disabled,
+    };
+
+    ImeEvent { window_id, action }
+}
+
 fn key_id(keycode: Option<&VirtualKeyCode>) -> KeyCode {
     match keycode {
         Some(k) => match k {
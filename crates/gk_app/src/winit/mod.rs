@@ -5,6 +5,7 @@ mod keyboard;
 mod manager;
 mod mouse;
 mod runner;
+mod touch;
 mod utils;
 mod window;
 
@@ -1,8 +1,12 @@
 use super::utils::win_id;
-use crate::winit::{keyboard, mouse};
+use crate::touch::dispatch_touch;
+use crate::winit::{keyboard, mouse, touch};
 use crate::App;
 use gk_sys::event::DrawEvent;
-use gk_sys::window::{GKWindow, WindowAction, WindowEvent, WindowId};
+use gk_sys::keyboard::KeyModifiers;
+use gk_sys::window::{
+    FileDropAction, FileDropEvent, GKWindow, WindowAction, WindowEvent, WindowId,
+};
 use gk_sys::{GKState, System};
 use hashbrown::HashMap;
 use winit::event::{Event, WindowEvent as WWindowEvent};
@@ -89,6 +93,7 @@ pub fn runner<S: GKState + 'static>(mut sys: System<S>) -> Result<(), String> {
 
     // track some inner data
     let mut inner_window_list = InnerWindowList::default();
+    let mut modifiers = KeyModifiers::default();
     event_loop.run(move |evt, event_loop, control_flow| {
         sys.get_mut_plugin::<App>()
             .unwrap()
@@ -147,7 +152,21 @@ pub fn runner<S: GKState + 'static>(mut sys: System<S>) -> Result<(), String> {
                     match event {
                         // keyboard events
                         WWindowEvent::KeyboardInput { input, .. } => {
-                            let evt = keyboard::process(id, input);
+                            let evt = keyboard::process(id, input, modifiers);
+                            sys.event(evt);
+                        }
+                        WWindowEvent::ModifiersChanged(state) => {
+                            let evt = keyboard::process_modifiers(id, state);
+                            modifiers = evt.modifiers;
+                            sys.event(evt);
+                        }
+                        WWindowEvent::ReceivedCharacter(c) => {
+                            if let Some(evt) = keyboard::process_text(id, c) {
+                                sys.event(evt);
+                            }
+                        }
+                        WWindowEvent::Ime(ime) => {
+                            let evt = keyboard::process_ime(id, ime);
                             sys.event(evt);
                         }
 
@@ -189,6 +208,20 @@ pub fn runner<S: GKState + 'static>(mut sys: System<S>) -> Result<(), String> {
                             sys.event(evt);
                         }
 
+                        // touch events
+                        WWindowEvent::Touch(t) => {
+                            let evt = touch::process_touch(id, t, scale_factor);
+                            dispatch_touch(&mut sys, evt);
+                        }
+                        WWindowEvent::TouchpadMagnify { delta, phase, .. } => {
+                            let evt = touch::process_magnify(id, delta, phase);
+                            sys.event(evt);
+                        }
+                        WWindowEvent::TouchpadRotate { delta, phase, .. } => {
+                            let evt = touch::process_rotate(id, delta, phase);
+                            sys.event(evt);
+                        }
+
                         // window events
                         WWindowEvent::Resized(size) => {
                             let size = size.to_logical::<u32>(scale_factor);
@@ -218,10 +251,24 @@ pub fn runner<S: GKState + 'static>(mut sys: System<S>) -> Result<(), String> {
                             });
                         }
                         WWindowEvent::Destroyed => {}
-                        WWindowEvent::DroppedFile(_) => {}
-                        WWindowEvent::HoveredFile(_) => {}
-                        WWindowEvent::HoveredFileCancelled => {}
-                        WWindowEvent::ReceivedCharacter(_) => {}
+                        WWindowEvent::DroppedFile(path) => {
+                            sys.event(FileDropEvent {
+                                id,
+                                action: FileDropAction::Dropped { path },
+                            });
+                        }
+                        WWindowEvent::HoveredFile(path) => {
+                            sys.event(FileDropEvent {
+                                id,
+                                action: FileDropAction::Hovered { path },
+                            });
+                        }
+                        WWindowEvent::HoveredFileCancelled => {
+                            sys.event(FileDropEvent {
+                                id,
+                                action: FileDropAction::Cancelled,
+                            });
+                        }
                         WWindowEvent::Focused(focus) => {
                             sys.event(WindowEvent {
                                 id,
@@ -233,19 +280,14 @@ pub fn runner<S: GKState + 'static>(mut sys: System<S>) -> Result<(), String> {
                             });
                         }
                         WWindowEvent::KeyboardInput { .. } => {}
-                        WWindowEvent::ModifiersChanged(_) => {}
-                        WWindowEvent::Ime(_) => {}
                         WWindowEvent::CursorMoved { .. } => {}
                         WWindowEvent::CursorEntered { .. } => {}
                         WWindowEvent::CursorLeft { .. } => {}
                         WWindowEvent::MouseWheel { .. } => {}
                         WWindowEvent::MouseInput { .. } => {}
-                        WWindowEvent::TouchpadMagnify { .. } => {}
                         WWindowEvent::SmartMagnify { .. } => {}
-                        WWindowEvent::TouchpadRotate { .. } => {}
                         WWindowEvent::TouchpadPressure { .. } => {}
                         WWindowEvent::AxisMotion { .. } => {}
-                        WWindowEvent::Touch(_) => {}
                         WWindowEvent::ScaleFactorChanged {
                             scale_factor,
                             new_inner_size,
@@ -0,0 +1,44 @@
+use gk_sys::touch::{GestureAction, GestureEvent, TouchEvent, TouchPhase};
+use gk_sys::window::WindowId;
+use winit::event::{Touch, TouchPhase as WTouchPhase};
+
+pub(crate) fn process_touch(window_id: WindowId, touch: Touch, scale_factor: f64) -> TouchEvent {
+    let pos = touch.location.to_logical::<f32>(scale_factor);
+    TouchEvent {
+        window_id,
+        id: touch.id,
+        phase: phase_id(touch.phase),
+        x: pos.x,
+        y: pos.y,
+        force: touch.force.map(|force| force.normalized() as _),
+    }
+}
+
+pub(crate) fn process_magnify(window_id: WindowId, delta: f64, phase: WTouchPhase) -> GestureEvent {
+    GestureEvent {
+        window_id,
+        action: GestureAction::Pinch {
+            delta: delta as _,
+            phase: phase_id(phase),
+        },
+    }
+}
+
+pub(crate) fn process_rotate(window_id: WindowId, delta: f32, phase: WTouchPhase) -> GestureEvent {
+    GestureEvent {
+        window_id,
+        action: GestureAction::Rotate {
+            delta,
+            phase: phase_id(phase),
+        },
+    }
+}
+
+fn phase_id(phase: WTouchPhase) -> TouchPhase {
+    match phase {
+        WTouchPhase::Started => TouchPhase::Started,
+        WTouchPhase::Moved => TouchPhase::Moved,
+        WTouchPhase::Ended => TouchPhase::Ended,
+        WTouchPhase::Cancelled => TouchPhase::Cancelled,
+    }
+}
@@ -15,6 +15,7 @@ pub struct Window {
     transparent: bool,
     min_size: Option<(u32, u32)>,
     max_size: Option<(u32, u32)>,
+    ime_allowed: bool,
 }
 
 impl Window {
@@ -72,6 +73,7 @@ impl Window {
             transparent,
             min_size: None,
             max_size: None,
+            ime_allowed: false,
         };
         if fullscreen {
             win.set_fullscreen(true);
@@ -249,4 +251,17 @@ impl GKWindow for Window {
     fn request_redraw(&mut self) {
         self.raw.request_redraw();
     }
+
+    fn set_ime_allowed(&mut self, allowed: bool) {
+        self.ime_allowed = allowed;
+        self.raw.set_ime_allowed(allowed);
+    }
+
+    fn ime_allowed(&self) -> bool {
+        self.ime_allowed
+    }
+
+    fn set_ime_position(&mut self, x: i32, y: i32) {
+        self.raw.set_ime_position(LogicalPosition::new(x, y));
+    }
 }
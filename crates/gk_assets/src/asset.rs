@@ -0,0 +1,340 @@
+use crate::events::AssetLoad;
+use gk_sys::storage::{FromPlugins, Plugins};
+use gk_sys::{AppBuilder, BuildConfig, GKState, Plugin};
+use hashbrown::HashMap;
+use std::any::TypeId;
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::Arc;
+
+type ParserFn<T> = dyn Fn(&str, &[u8], &mut Plugins) -> Result<T, String>;
+
+/// Represents a type that can be loaded from a file
+pub trait Asset: Sized + 'static {
+    /// Parsers used by default for this type
+    fn parsers() -> AssetParsers<Self> {
+        AssetParsers::default()
+    }
+}
+
+/// Parsers for an asset type keyed by file extension
+pub struct AssetParsers<T> {
+    map: HashMap<String, Rc<ParserFn<T>>>,
+}
+
+impl<T> Default for AssetParsers<T> {
+    fn default() -> Self {
+        Self {
+            map: HashMap::default(),
+        }
+    }
+}
+
+impl<T: Asset> AssetParsers<T> {
+    /// Add a parser for the extensions passed in
+    pub fn add<F>(self, extensions: &[&str], parser: F) -> Self
+    where
+        F: Fn(&str, &[u8]) -> Result<T, String> + 'static,
+    {
+        self.insert(extensions, Rc::new(move |id, data, _| parser(id, data)))
+    }
+
+    /// Add a parser that needs a plugin to create the asset
+    pub fn add_using<P, F>(self, extensions: &[&str], parser: F) -> Self
+    where
+        P: 'static,
+        F: Fn(&str, &[u8], &mut P) -> Result<T, String> + 'static,
+    {
+        self.insert(
+            extensions,
+            Rc::new(move |id, data, plugins| {
+                let plugin = plugins.get_mut::<P>().ok_or_else(|| {
+                    format!(
+                        "Plugin '{}' needed to parse '{}' is not registered",
+                        std::any::type_name::<P>(),
+                        id
+                    )
+                })?;
+                parser(id, data, plugin)
+            }),
+        )
+    }
+
+    fn insert(mut self, extensions: &[&str], parser: Rc<ParserFn<T>>) -> Self {
+        extensions.iter().for_each(|ext| {
+            self.map.insert(ext.to_lowercase(), parser.clone());
+        });
+        self
+    }
+
+    fn get(&self, id: &str) -> Option<Rc<ParserFn<T>>> {
+        let ext = Path::new(id).extension()?.to_str()?.to_lowercase();
+        self.map.get(&ext).cloned()
+    }
+}
+
+/// Reference to a typed asset loaded by `AssetLoader`
+pub struct Handle<T> {
+    id: Arc<str>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Handle<T> {
+    pub(crate) fn new(id: &str) -> Self {
+        Self {
+            id: id.into(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Id used to load the asset
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Debug for Handle<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Handle<{}>({})", std::any::type_name::<T>(), self.id)
+    }
+}
+
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+impl<T> Hash for Handle<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// Loading state of an asset
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadState {
+    Loading,
+    Loaded,
+    Failed(String),
+}
+
+enum Slot<T> {
+    Loaded(T),
+    Failed(String),
+}
+
+/// Store of parsed assets of type `T`
+pub struct Assets<T> {
+    parsers: AssetParsers<T>,
+    slots: HashMap<String, Slot<T>>,
+}
+
+impl<T: Asset> Assets<T> {
+    pub fn config() -> AssetsConfig<T> {
+        AssetsConfig {
+            parsers: Some(T::parsers()),
+        }
+    }
+
+    /// Returns the asset if it's loaded
+    pub fn get(&self, handle: &Handle<T>) -> Option<&T> {
+        match self.slots.get(handle.id()) {
+            Some(Slot::Loaded(asset)) => Some(asset),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the asset if it's loaded
+    pub fn get_mut(&mut self, handle: &Handle<T>) -> Option<&mut T> {
+        match self.slots.get_mut(handle.id()) {
+            Some(Slot::Loaded(asset)) => Some(asset),
+            _ => None,
+        }
+    }
+
+    /// Loading state of the asset, cancelled loads stay as `Loading` because nothing is stored
+    pub fn state(&self, handle: &Handle<T>) -> LoadState {
+        match self.slots.get(handle.id()) {
+            None => LoadState::Loading,
+            Some(Slot::Loaded(_)) => LoadState::Loaded,
+            Some(Slot::Failed(err)) => LoadState::Failed(err.clone()),
+        }
+    }
+
+    /// Returns true if the asset is loaded and parsed
+    pub fn is_loaded(&self, handle: &Handle<T>) -> bool {
+        matches!(self.slots.get(handle.id()), Some(Slot::Loaded(_)))
+    }
+
+    /// Removes the asset from the store returning it
+    pub fn remove(&mut self, handle: &Handle<T>) -> Option<T> {
+        match self.slots.remove(handle.id()) {
+            Some(Slot::Loaded(asset)) => Some(asset),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn insert(&mut self, id: &str, result: Result<T, String>) {
+        let slot = match result {
+            Ok(asset) => Slot::Loaded(asset),
+            Err(err) => {
+                log::warn!("{}", err);
+                Slot::Failed(err)
+            }
+        };
+        self.slots.insert(id.to_string(), slot);
+    }
+
+    /// A failed reload keeps the previous slot so the asset can still be used
+    pub(crate) fn reload(&mut self, id: &str, result: Result<T, String>) {
+        match result {
+            Err(err) if self.slots.contains_key(id) => {
+                log::error!("Cannot reload '{}', keeping the previous one: {}", id, err)
+            }
+            _ => self.insert(id, result),
+        }
+    }
+}
+
+impl<T: 'static> Plugin for Assets<T> {}
+
+pub struct AssetsConfig<T> {
+    parsers: Option<AssetParsers<T>>,
+}
+
+impl<T: Asset> AssetsConfig<T> {
+    /// Add a parser for the extensions passed in
+    pub fn with_parser<F>(mut self, extensions: &[&str], parser: F) -> Self
+    where
+        F: Fn(&str, &[u8]) -> Result<T, String> + 'static,
+    {
+        self.parsers = self.parsers.map(|p| p.add(extensions, parser));
+        self
+    }
+
+    /// Add a parser that needs a plugin to create the asset
+    pub fn with_parser_using<P, F>(mut self, extensions: &[&str], parser: F) -> Self
+    where
+        P: 'static,
+        F: Fn(&str, &[u8], &mut P) -> Result<T, String> + 'static,
+    {
+        self.parsers = self.parsers.map(|p| p.add_using(extensions, parser));
+        self
+    }
+}
+
+impl<S: GKState + 'static, T: Asset> BuildConfig<S> for AssetsConfig<T> {
+    fn apply(&mut self, builder: AppBuilder<S>) -> Result<AppBuilder<S>, String> {
+        let parsers = self
+            .parsers
+            .take()
+            .ok_or_else(|| "AssetsConfig cannot be applied twice".to_string())?;
+
+        let assets = Assets {
+            parsers,
+            slots: HashMap::default(),
+        };
+
+        let builder = builder.on(on_asset_load::<T>);
+        Ok(builder.add_plugin(assets))
+    }
+}
+
+fn on_asset_load<T: Asset>(evt: &AssetLoad, plugins: &mut Plugins) {
+    if evt.type_id != Some(TypeId::of::<T>()) {
+        return;
+    }
+
+    let id = evt.id();
+    let parser = Assets::<T>::from_plugins(plugins).parsers.get(id);
+    let result = match (evt.data(), parser) {
+        (Ok(data), Some(parser)) => {
+            parser(id, data, plugins).map_err(|err| format!("Cannot parse '{}': {}", id, err))
+        }
+        (Ok(_), None) => Err(format!(
+            "There is no parser for '{}' as {}",
+            id,
+            std::any::type_name::<T>()
+        )),
+        (Err(err), _) => Err(err.to_string()),
+    };
+
+    let assets = Assets::<T>::from_plugins(plugins);
+    if evt.is_reload() {
+        assets.reload(id, result);
+    } else {
+        assets.insert(id, result);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Text(String);
+
+    impl Asset for Text {
+        fn parsers() -> AssetParsers<Self> {
+            AssetParsers::default().add(&["txt", "MD"], |_, data| {
+                String::from_utf8(data.to_vec())
+                    .map(Text)
+                    .map_err(|err| err.to_string())
+            })
+        }
+    }
+
+    #[test]
+    fn test_parser_by_extension() {
+        let parsers = Text::parsers();
+        assert!(parsers.get("./assets/readme.md").is_some());
+        assert!(parsers.get("./assets/hello.TXT").is_some());
+        assert!(parsers.get("./assets/image.png").is_none());
+        assert!(parsers.get("./assets/no_extension").is_none());
+    }
+
+    #[test]
+    fn test_assets_state() {
+        let mut assets = Assets {
+            parsers: Text::parsers(),
+            slots: HashMap::default(),
+        };
+
+        let hello = Handle::<Text>::new("hello.txt");
+        let broken = Handle::<Text>::new("broken.txt");
+        assert_eq!(assets.state(&hello), LoadState::Loading);
+
+        assets.insert(hello.id(), Ok(Text("hello".to_string())));
+        assets.insert(broken.id(), Err("broken".to_string()));
+        assert_eq!(assets.state(&hello), LoadState::Loaded);
+        assert_eq!(assets.get(&hello).map(|t| t.0.as_str()), Some("hello"));
+        assert_eq!(
+            assets.state(&broken),
+            LoadState::Failed("broken".to_string())
+        );
+        assert!(assets.get(&broken).is_none());
+
+        assets.reload(hello.id(), Err("broken".to_string()));
+        assert_eq!(assets.get(&hello).map(|t| t.0.as_str()), Some("hello"));
+        assets.reload(hello.id(), Ok(Text("bye".to_string())));
+        assert_eq!(assets.get(&hello).map(|t| t.0.as_str()), Some("bye"));
+
+        let new = Handle::<Text>::new("new.txt");
+        assets.reload(new.id(), Err("broken".to_string()));
+        assert_eq!(assets.state(&new), LoadState::Failed("broken".to_string()));
+    }
+}
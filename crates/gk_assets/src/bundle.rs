@@ -0,0 +1,257 @@
+use hashbrown::HashMap;
+
+/// Magic bytes at the start of a bundle file
+const MAGIC: &[u8; 4] = b"GKPK";
+/// Version of the bundle format
+const VERSION: u8 = 1;
+/// The payload is compressed with zstd
+const FLAG_ZSTD: u8 = 1;
+
+/// Pack of files that can be mounted by `AssetLoader` as a virtual filesystem
+///
+/// The format is a small header (`GKPK`, version, flags), an index with the
+/// path, offset and size of each file, and the payload with the files' data.
+/// The payload can be compressed with zstd, this needs the `zstd` feature.
+#[derive(Debug, Clone)]
+pub struct Bundle {
+    /// Start and end of each file inside the payload
+    entries: HashMap<String, (usize, usize)>,
+    payload: Vec<u8>,
+}
+
+impl Bundle {
+    /// Create a bundle from the bytes of a bundle file
+    pub fn from_bytes(data: &[u8]) -> Result<Self, String> {
+        let mut reader = Reader { data, pos: 0 };
+        if reader.take(4)? != MAGIC {
+            return Err("Invalid bundle file".to_string());
+        }
+
+        let version = reader.u8()?;
+        if version != VERSION {
+            return Err(format!("Unsupported bundle version {}", version));
+        }
+
+        let flags = reader.u8()?;
+        let _reserved = reader.take(2)?;
+
+        let len = reader.u32()? as usize;
+        // the number of entries comes from the file, it's not used to pre-allocate
+        let mut entries = HashMap::new();
+        for _ in 0..len {
+            let path_len = reader.u16()? as usize;
+            let path = std::str::from_utf8(reader.take(path_len)?)
+                .map_err(|err| err.to_string())?
+                .to_string();
+            let offset = reader.u64()?;
+            let size = reader.u64()?;
+            entries.insert(path, (offset, size));
+        }
+
+        let payload = reader.take(data.len() - reader.pos)?;
+        let payload = if flags & FLAG_ZSTD != 0 {
+            decompress(payload)?
+        } else {
+            payload.to_vec()
+        };
+
+        let entries = entries
+            .into_iter()
+            .map(|(path, (offset, size))| {
+                let range = offset.checked_add(size).and_then(|end| {
+                    let start = usize::try_from(offset).ok()?;
+                    let end = usize::try_from(end).ok()?;
+                    (end <= payload.len()).then_some((start, end))
+                });
+                match range {
+                    Some(range) => Ok((path, range)),
+                    None => Err(format!("Bundle entry '{}' is out of bounds", path)),
+                }
+            })
+            .collect::<Result<HashMap<_, _>, String>>()?;
+
+        Ok(Self { entries, payload })
+    }
+
+    /// Returns the data of the file if it's inside the bundle
+    pub fn get(&self, path: &str) -> Option<&[u8]> {
+        let (start, end) = *self.entries.get(normalize(path))?;
+        Some(&self.payload[start..end])
+    }
+
+    /// Returns true if the bundle has the file
+    pub fn contains(&self, path: &str) -> bool {
+        self.entries.contains_key(normalize(path))
+    }
+
+    /// Paths of the files inside the bundle
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(|path| path.as_str())
+    }
+}
+
+/// Creates the bytes of a bundle file
+#[derive(Debug, Default, Clone)]
+pub struct BundleWriter {
+    files: Vec<(String, Vec<u8>)>,
+    compress: bool,
+}
+
+impl BundleWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file to the bundle
+    pub fn with_file(mut self, path: &str, data: &[u8]) -> Self {
+        self.files
+            .push((normalize(path).to_string(), data.to_vec()));
+        self
+    }
+
+    /// Compress the payload with zstd, it needs the `zstd` feature
+    pub fn with_compression(mut self, enable: bool) -> Self {
+        self.compress = enable;
+        self
+    }
+
+    pub fn build(self) -> Result<Vec<u8>, String> {
+        let mut header = vec![];
+        header.extend_from_slice(MAGIC);
+        header.push(VERSION);
+        header.push(if self.compress { FLAG_ZSTD } else { 0 });
+        header.extend_from_slice(&[0, 0]);
+        header.extend_from_slice(&(self.files.len() as u32).to_le_bytes());
+
+        let mut payload = vec![];
+        for (path, data) in &self.files {
+            let path_len = u16::try_from(path.len())
+                .map_err(|_| format!("Bundle path '{}' is too long", path))?;
+            header.extend_from_slice(&path_len.to_le_bytes());
+            header.extend_from_slice(path.as_bytes());
+            header.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+            header.extend_from_slice(&(data.len() as u64).to_le_bytes());
+            payload.extend_from_slice(data);
+        }
+
+        let payload = if self.compress {
+            compress(&payload)?
+        } else {
+            payload
+        };
+
+        header.extend(payload);
+        Ok(header)
+    }
+}
+
+/// Paths inside the bundle never start with `./` or `/`
+fn normalize(path: &str) -> &str {
+    path.trim_start_matches("./").trim_start_matches('/')
+}
+
+#[cfg(feature = "zstd")]
+fn compress(data: &[u8]) -> Result<Vec<u8>, String> {
+    zstd::encode_all(data, 0).map_err(|err| err.to_string())
+}
+
+#[cfg(not(feature = "zstd"))]
+fn compress(_data: &[u8]) -> Result<Vec<u8>, String> {
+    Err("Bundle compression needs the 'zstd' feature".to_string())
+}
+
+#[cfg(feature = "zstd")]
+fn decompress(data: &[u8]) -> Result<Vec<u8>, String> {
+    zstd::decode_all(data).map_err(|err| err.to_string())
+}
+
+#[cfg(not(feature = "zstd"))]
+fn decompress(_data: &[u8]) -> Result<Vec<u8>, String> {
+    Err("Compressed bundles need the 'zstd' feature".to_string())
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let bytes = self
+            .data
+            .get(self.pos..self.pos.saturating_add(len))
+            .ok_or_else(|| "Unexpected end of bundle file".to_string())?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bundle() {
+        let data = BundleWriter::new()
+            .with_file("sprites/hero.png", &[1, 2, 3])
+            .with_file("./levels/1.json", b"{}")
+            .build()
+            .unwrap();
+
+        let bundle = Bundle::from_bytes(&data).unwrap();
+        assert_eq!(bundle.get("sprites/hero.png"), Some([1u8, 2, 3].as_slice()));
+        assert_eq!(bundle.get("./levels/1.json"), Some(b"{}".as_slice()));
+        assert!(bundle.contains("/sprites/hero.png"));
+        assert!(bundle.get("missing.png").is_none());
+        assert_eq!(bundle.paths().count(), 2);
+    }
+
+    #[test]
+    fn test_invalid_bundle() {
+        assert!(Bundle::from_bytes(b"PK\x03\x04").is_err());
+
+        let data = BundleWriter::new()
+            .with_file("a.txt", b"hello")
+            .build()
+            .unwrap();
+        assert!(Bundle::from_bytes(&data[..data.len() - 1]).is_err());
+
+        // the offset of the entry is right after the header and the path
+        let mut data = data;
+        data[19..27].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(
+            Bundle::from_bytes(&data).unwrap_err(),
+            "Bundle entry 'a.txt' is out of bounds"
+        );
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_compressed_bundle() {
+        let data = BundleWriter::new()
+            .with_file("a.txt", &[7; 1024])
+            .with_compression(true)
+            .build()
+            .unwrap();
+        assert!(data.len() < 1024);
+
+        let bundle = Bundle::from_bytes(&data).unwrap();
+        assert_eq!(bundle.get("a.txt"), Some([7u8; 1024].as_slice()));
+    }
+}
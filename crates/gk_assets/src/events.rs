@@ -1,7 +1,11 @@
+use std::any::TypeId;
+
 #[derive(Clone, Debug)]
 pub struct AssetLoad {
     pub(crate) id: String,
     pub(crate) state: AssetState,
+    pub(crate) type_id: Option<TypeId>,
+    pub(crate) reload: bool,
 }
 
 impl AssetLoad {
@@ -10,6 +14,11 @@ impl AssetLoad {
         &self.id
     }
 
+    /// Returns true if the file was loaded again because it changed (hot reload)
+    pub fn is_reload(&self) -> bool {
+        self.reload
+    }
+
     /// Blob buffer
     pub fn data(&self) -> Result<&[u8], String> {
         match &self.state {
@@ -19,6 +28,20 @@ impl AssetLoad {
     }
 }
 
+/// The file was modified and loaded again, dispatched after its `AssetLoad`
+#[derive(Clone, Debug)]
+pub struct AssetReloaded {
+    pub id: String,
+}
+
+/// Every file of the group finished loading
+#[derive(Clone, Debug)]
+pub struct AssetGroupLoaded {
+    pub name: String,
+    pub loaded: usize,
+    pub failed: usize,
+}
+
 #[derive(Clone, Debug)]
 pub(crate) enum AssetState {
     Loaded(Vec<u8>),
@@ -0,0 +1,49 @@
+use crate::asset::{Asset, AssetParsers};
+
+/// Font file data (TrueType, OpenType or collections of them)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Font {
+    data: Vec<u8>,
+}
+
+impl Font {
+    /// Create the font checking that the data is a valid font file
+    pub fn new(data: &[u8]) -> Result<Self, String> {
+        let is_font = matches!(
+            data.get(0..4),
+            Some([0x00, 0x01, 0x00, 0x00] | b"OTTO" | b"true" | b"ttcf")
+        );
+
+        if !is_font {
+            return Err("Invalid font format".to_string());
+        }
+
+        Ok(Self {
+            data: data.to_vec(),
+        })
+    }
+
+    /// Raw bytes of the font
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl Asset for Font {
+    fn parsers() -> AssetParsers<Self> {
+        AssetParsers::default().add(&["ttf", "otf", "ttc"], |_, data| Font::new(data))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_font_format() {
+        assert!(Font::new(&[0x00, 0x01, 0x00, 0x00, 0x10]).is_ok());
+        assert!(Font::new(b"OTTO....").is_ok());
+        assert!(Font::new(b"\x89PNG").is_err());
+        assert!(Font::new(&[]).is_err());
+    }
+}
@@ -0,0 +1,118 @@
+/// Progress of a named set of loads
+#[derive(Clone, Debug, Default)]
+pub struct AssetGroup {
+    pending: Vec<String>,
+    total: usize,
+    loaded: usize,
+    failed: usize,
+    notified: bool,
+}
+
+impl AssetGroup {
+    /// Number of files added to the group
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Number of files loaded successfully
+    pub fn loaded(&self) -> usize {
+        self.loaded
+    }
+
+    /// Number of files that failed to load
+    pub fn failed(&self) -> usize {
+        self.failed
+    }
+
+    /// Files still loading
+    pub fn pending(&self) -> &[String] {
+        &self.pending
+    }
+
+    /// Returns true once every file of the group is loaded or failed
+    pub fn is_done(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Value from 0.0 to 1.0, failed files count as processed
+    pub fn progress(&self) -> f32 {
+        if self.total == 0 {
+            return 1.0;
+        }
+
+        (self.loaded + self.failed) as f32 / self.total as f32
+    }
+
+    pub(crate) fn add(&mut self, id: &str) {
+        if self.pending.iter().any(|p| p == id) {
+            return;
+        }
+
+        self.pending.push(id.to_string());
+        self.total += 1;
+        self.notified = false;
+    }
+
+    pub(crate) fn complete(&mut self, id: &str, success: bool) {
+        if !self.remove_pending(id) {
+            return;
+        }
+
+        if success {
+            self.loaded += 1;
+        } else {
+            self.failed += 1;
+        }
+    }
+
+    pub(crate) fn cancel(&mut self, id: &str) {
+        if self.remove_pending(id) {
+            self.total -= 1;
+        }
+    }
+
+    /// Returns true only the first time the group is done since the last file was added
+    pub(crate) fn take_done(&mut self) -> bool {
+        if self.notified || !self.is_done() {
+            return false;
+        }
+
+        self.notified = true;
+        true
+    }
+
+    fn remove_pending(&mut self, id: &str) -> bool {
+        let len = self.pending.len();
+        self.pending.retain(|p| p != id);
+        self.pending.len() != len
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_group_progress() {
+        let mut group = AssetGroup::default();
+        group.add("a.png");
+        group.add("b.png");
+        group.add("c.png");
+        group.add("c.png");
+        assert_eq!(group.total(), 3);
+        assert_eq!(group.progress(), 0.0);
+
+        group.complete("a.png", true);
+        group.complete("b.png", false);
+        assert_eq!(group.loaded(), 1);
+        assert_eq!(group.failed(), 1);
+        assert!(!group.is_done());
+        assert!(!group.take_done());
+
+        group.cancel("c.png");
+        assert_eq!(group.total(), 2);
+        assert_eq!(group.progress(), 1.0);
+        assert!(group.take_done());
+        assert!(!group.take_done());
+    }
+}
@@ -0,0 +1,37 @@
+use crate::asset::{Asset, AssetParsers};
+use serde::de::DeserializeOwned;
+use std::ops::{Deref, DerefMut};
+
+/// Any deserializable type loaded from a JSON file
+#[derive(Clone, Debug, PartialEq)]
+pub struct Json<T>(pub T);
+
+impl<T> Json<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Json<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Json<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: DeserializeOwned + 'static> Asset for Json<T> {
+    fn parsers() -> AssetParsers<Self> {
+        AssetParsers::default().add(&["json"], |_, data| {
+            serde_json::from_slice(data)
+                .map(Json)
+                .map_err(|err| err.to_string())
+        })
+    }
+}
@@ -1,7 +1,22 @@
+mod asset;
+mod bundle;
 mod events;
+mod font;
+mod group;
+#[cfg(feature = "serde")]
+mod json;
 mod load_file;
 mod loader;
+mod user_data;
 mod waker;
+mod watcher;
 
-pub use events::AssetLoad;
+pub use asset::{Asset, AssetParsers, Assets, AssetsConfig, Handle, LoadState};
+pub use bundle::{Bundle, BundleWriter};
+pub use events::{AssetGroupLoaded, AssetLoad, AssetReloaded};
+pub use font::Font;
+pub use group::AssetGroup;
+#[cfg(feature = "serde")]
+pub use json::Json;
 pub use loader::{AssetLoader, AssetLoaderConfig};
+pub use user_data::*;
@@ -1,15 +1,35 @@
 use super::waker::*;
-use crate::events::{AssetLoad, AssetState};
+use crate::asset::{Asset, Handle};
+use crate::bundle::Bundle;
+use crate::events::{AssetGroupLoaded, AssetLoad, AssetReloaded, AssetState};
+use crate::group::AssetGroup;
 use crate::load_file::FileLoader;
-use futures::future::LocalBoxFuture;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::watcher::FileWatcher;
+use futures::future::{ready, LocalBoxFuture};
 use futures::task::{Context, Poll};
+use gk_sys::window::{FileDropAction, FileDropEvent};
 use gk_sys::{event, AppBuilder, BuildConfig, EventQueue, GKState, Plugin};
+use hashbrown::HashMap;
+use std::any::TypeId;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
 
 // TODO url loader
 
+/// Time between checks of the watched files
+#[cfg(not(target_arch = "wasm32"))]
+const HOT_RELOAD_INTERVAL: Duration = Duration::from_millis(500);
+
 pub struct AssetLoader {
     loading: Vec<LoadWrapper>,
     file_loader: FileLoader,
+    bundles: Vec<Bundle>,
+    mounting: Vec<LoadWrapper>,
+    deferred: Vec<(String, Option<TypeId>)>,
+    groups: HashMap<String, AssetGroup>,
+    #[cfg(not(target_arch = "wasm32"))]
+    watcher: Option<FileWatcher>,
 }
 
 impl AssetLoader {
@@ -18,11 +38,36 @@ impl AssetLoader {
     }
 
     pub(crate) fn update<S: GKState + 'static>(&mut self, events: &mut EventQueue<S>) {
+        self.update_mounting();
+
         let mut needs_clean = true;
 
         self.loading.iter_mut().for_each(|loader| {
             if let Some(loaded) = loader.try_load() {
+                #[cfg(not(target_arch = "wasm32"))]
+                if let Some(watcher) = &mut self.watcher {
+                    if loaded.data().is_ok() {
+                        watcher.watch(&loaded.id, loaded.type_id);
+                    } else {
+                        watcher.discard(&loaded.id, loaded.type_id);
+                    }
+                }
+
+                let reloaded = loaded.reload.then(|| AssetReloaded {
+                    id: loaded.id.clone(),
+                });
+
+                if !loaded.reload {
+                    let success = loaded.data().is_ok();
+                    self.groups
+                        .values_mut()
+                        .for_each(|group| group.complete(&loaded.id, success));
+                }
+
                 events.queue(loaded);
+                if let Some(reloaded) = reloaded {
+                    events.queue(reloaded);
+                }
                 needs_clean = true;
             }
         });
@@ -30,20 +75,256 @@ impl AssetLoader {
         if needs_clean {
             self.loading.retain(|loader| !loader.is_loaded());
         }
+
+        self.groups.iter_mut().for_each(|(name, group)| {
+            if group.take_done() {
+                events.queue(AssetGroupLoaded {
+                    name: name.clone(),
+                    loaded: group.loaded(),
+                    failed: group.failed(),
+                });
+            }
+        });
+
+        #[cfg(not(target_arch = "wasm32"))]
+        self.reload_changed_files();
     }
 
-    pub fn load(&mut self, file_path: &str) -> &mut Self {
-        log::info!("Loading file '{}'", file_path);
-        let fut = Box::pin(self.file_loader.load_file(file_path));
-        self.loading.push(LoadWrapper::new(file_path, fut));
+    fn update_mounting(&mut self) {
+        if self.mounting.is_empty() {
+            return;
+        }
+
+        self.mounting.iter_mut().for_each(|loader| {
+            if let Some(loaded) = loader.try_load() {
+                match loaded.data().and_then(Bundle::from_bytes) {
+                    Ok(bundle) => {
+                        log::info!("Bundle '{}' mounted", loaded.id);
+                        self.bundles.push(bundle);
+                    }
+                    Err(err) => log::error!("Cannot mount bundle '{}': {}", loaded.id, err),
+                }
+            }
+        });
+        self.mounting.retain(|loader| !loader.is_loaded());
+
+        // files requested while mounting are loaded once all bundles are ready
+        if self.mounting.is_empty() {
+            std::mem::take(&mut self.deferred)
+                .into_iter()
+                .for_each(|(path, type_id)| self.load_file(&path, type_id, false));
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn reload_changed_files(&mut self) {
+        let changed = match &mut self.watcher {
+            Some(watcher) => watcher.changed(),
+            None => return,
+        };
+
+        changed.into_iter().for_each(|(path, type_id)| {
+            // skip it if the file is still loading
+            let loading = self
+                .loading
+                .iter()
+                .any(|loader| loader.id == path && loader.type_id == type_id);
+            if !loading {
+                self.load_file(&path, type_id, true);
+            }
+        });
+    }
+
+    /// Load the file as an asset of type `T`, the `Assets<T>` store will keep it once parsed
+    pub fn load<T: Asset>(&mut self, file_path: &str) -> Handle<T> {
+        self.load_file(file_path, Some(TypeId::of::<T>()), false);
+        Handle::new(file_path)
+    }
+
+    /// Load the file as bytes, an `AssetLoad` event will be dispatched once loaded
+    pub fn load_raw(&mut self, file_path: &str) -> &mut Self {
+        self.load_file(file_path, None, false);
         self
     }
+
+    /// Load the file as an asset of type `T` tracking it as part of the group `name`
+    pub fn load_in<T: Asset>(&mut self, name: &str, file_path: &str) -> Handle<T> {
+        self.add_to_group(name, file_path);
+        self.load(file_path)
+    }
+
+    /// Load the file as bytes tracking it as part of the group `name`
+    pub fn load_raw_in(&mut self, name: &str, file_path: &str) -> &mut Self {
+        self.add_to_group(name, file_path);
+        self.load_raw(file_path)
+    }
+
+    /// Progress of the group, it's kept until `cancel_group` or `remove_group` is called
+    pub fn group(&self, name: &str) -> Option<&AssetGroup> {
+        self.groups.get(name)
+    }
+
+    /// Stop tracking the group without cancelling its loads
+    pub fn remove_group(&mut self, name: &str) -> Option<AssetGroup> {
+        self.groups.remove(name)
+    }
+
+    /// Cancel the file if it's still loading, returns false if there was nothing to cancel
+    /// No `AssetLoad` event will be dispatched for it, so `Assets<T>` keeps no state for
+    /// its handles and `Assets::state` returns `LoadState::Loading` until it's loaded again
+    pub fn cancel(&mut self, file_path: &str) -> bool {
+        let loading = self.loading.len();
+        let deferred = self.deferred.len();
+        self.loading
+            .retain(|loader| loader.reload || loader.id != file_path);
+        self.deferred.retain(|(path, _)| path != file_path);
+
+        let cancelled = loading != self.loading.len() || deferred != self.deferred.len();
+        if cancelled {
+            log::info!("Cancelled file '{}'", file_path);
+            self.groups
+                .values_mut()
+                .for_each(|group| group.cancel(file_path));
+        }
+
+        cancelled
+    }
+
+    /// Cancel the pending files of the group and stop tracking it
+    /// Files also requested by other groups or by plain loads keep loading for them,
+    /// the cancelled handles get no state in `Assets<T>` like with `cancel`
+    pub fn cancel_group(&mut self, name: &str) -> Option<AssetGroup> {
+        let group = self.groups.remove(name)?;
+        // each request has its own load, the ones of other groups or plain loads are kept
+        group
+            .pending()
+            .iter()
+            .for_each(|path| self.cancel_request(path));
+        Some(group)
+    }
+
+    /// Removes only one load of the file, the rest of requests for it keep loading
+    fn cancel_request(&mut self, file_path: &str) {
+        let loading = self
+            .loading
+            .iter()
+            .rposition(|loader| !loader.reload && loader.id == file_path);
+        if let Some(index) = loading {
+            self.loading.remove(index);
+        } else if let Some(index) = self
+            .deferred
+            .iter()
+            .rposition(|(path, _)| path == file_path)
+        {
+            self.deferred.remove(index);
+        } else {
+            return;
+        }
+
+        if !self.is_requested(file_path) {
+            log::info!("Cancelled file '{}'", file_path);
+        }
+    }
+
+    fn is_requested(&self, file_path: &str) -> bool {
+        self.loading
+            .iter()
+            .any(|loader| !loader.reload && loader.id == file_path)
+            || self.deferred.iter().any(|(path, _)| path == file_path)
+    }
+
+    fn add_to_group(&mut self, name: &str, file_path: &str) {
+        self.groups
+            .entry(name.to_string())
+            .or_default()
+            .add(file_path);
+    }
+
+    /// Mount a bundle file, the files inside it will be loaded from the bundle
+    /// instead of the filesystem. Loads requested while mounting will wait for it
+    pub fn mount(&mut self, bundle_path: &str) -> &mut Self {
+        log::info!("Mounting bundle '{}'", bundle_path);
+        let fut = Box::pin(self.file_loader.load_file(bundle_path));
+        self.mounting
+            .push(LoadWrapper::new(bundle_path, None, false, fut));
+        self
+    }
+
+    /// Mount a bundle already in memory
+    pub fn mount_bundle(&mut self, bundle: Bundle) -> &mut Self {
+        self.bundles.push(bundle);
+        self
+    }
+
+    fn load_file(&mut self, file_path: &str, type_id: Option<TypeId>, reload: bool) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(watcher) = &mut self.watcher {
+            if reload || self.mounting.is_empty() {
+                watcher.prepare(file_path, type_id);
+            }
+        }
+
+        if reload {
+            log::info!("Reloading file '{}'", file_path);
+            // reloads always come from the filesystem
+            let fut = Box::pin(self.file_loader.load_file(file_path));
+            self.loading
+                .push(LoadWrapper::new(file_path, type_id, reload, fut));
+            return;
+        }
+
+        if !self.mounting.is_empty() {
+            self.deferred.push((file_path.to_string(), type_id));
+            return;
+        }
+
+        log::info!("Loading file '{}'", file_path);
+
+        // the last mounted bundle has priority
+        let bundled = self
+            .bundles
+            .iter()
+            .rev()
+            .find_map(|bundle| bundle.get(file_path));
+
+        let fut: LocalBoxFuture<'static, Result<Vec<u8>, String>> = match bundled {
+            Some(data) => Box::pin(ready(Ok(data.to_vec()))),
+            None => Box::pin(self.file_loader.load_file(file_path)),
+        };
+        self.loading
+            .push(LoadWrapper::new(file_path, type_id, reload, fut));
+    }
 }
 
 impl Plugin for AssetLoader {}
 
-#[derive(Debug, Default, Copy, Clone)]
-pub struct AssetLoaderConfig {}
+#[derive(Debug, Default, Clone)]
+pub struct AssetLoaderConfig {
+    load_dropped_files: bool,
+    hot_reload: bool,
+    bundles: Vec<String>,
+}
+
+impl AssetLoaderConfig {
+    /// Load the files dropped into any window, using the file's path as id
+    pub fn with_dropped_files(mut self, enable: bool) -> Self {
+        self.load_dropped_files = enable;
+        self
+    }
+
+    /// Load again the files modified on disk, dispatching `AssetLoad` and `AssetReloaded`
+    /// Only available on native platforms
+    pub fn with_hot_reload(mut self, enable: bool) -> Self {
+        self.hot_reload = enable;
+        self
+    }
+
+    /// Mount the bundle file at init
+    pub fn with_bundle(mut self, bundle_path: &str) -> Self {
+        self.bundles.push(bundle_path.to_string());
+        self
+    }
+}
 
 impl<S: GKState + 'static> BuildConfig<S> for AssetLoaderConfig {
     fn apply(&mut self, builder: AppBuilder<S>) -> Result<AppBuilder<S>, String> {
@@ -52,24 +333,61 @@ impl<S: GKState + 'static> BuildConfig<S> for AssetLoaderConfig {
                 loader.update(events)
             },
         );
-        let asset_loader = AssetLoader {
+
+        let builder = if self.load_dropped_files {
+            builder.on(|evt: &FileDropEvent, loader: &mut AssetLoader| {
+                if let FileDropAction::Dropped { path } = &evt.action {
+                    loader.load_raw(&path.to_string_lossy());
+                }
+            })
+        } else {
+            builder
+        };
+
+        #[cfg(target_arch = "wasm32")]
+        if self.hot_reload {
+            log::warn!("Assets hot reload is not available on web.");
+        }
+
+        let mut asset_loader = AssetLoader {
             loading: vec![],
             file_loader: FileLoader::new()?,
+            bundles: vec![],
+            mounting: vec![],
+            deferred: vec![],
+            groups: HashMap::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            watcher: self
+                .hot_reload
+                .then(|| FileWatcher::new(HOT_RELOAD_INTERVAL)),
         };
+        self.bundles.iter().for_each(|path| {
+            asset_loader.mount(path);
+        });
+
         Ok(builder.add_plugin(asset_loader))
     }
 }
 
 struct LoadWrapper {
     id: String,
+    type_id: Option<TypeId>,
+    reload: bool,
     fut: LocalBoxFuture<'static, Result<Vec<u8>, String>>,
     loaded: bool,
 }
 
 impl LoadWrapper {
-    pub fn new(id: &str, fut: LocalBoxFuture<'static, Result<Vec<u8>, String>>) -> Self {
+    pub fn new(
+        id: &str,
+        type_id: Option<TypeId>,
+        reload: bool,
+        fut: LocalBoxFuture<'static, Result<Vec<u8>, String>>,
+    ) -> Self {
         Self {
             id: id.to_string(),
+            type_id,
+            reload,
             fut,
             loaded: false,
         }
@@ -85,6 +403,8 @@ impl LoadWrapper {
                     Ok(buff) => Some(AssetLoad {
                         id: self.id.clone(),
                         state: AssetState::Loaded(buff),
+                        type_id: self.type_id,
+                        reload: self.reload,
                     }),
                     Err(err) => {
                         let err = format!("Cannot load file: {}: {}", self.id, err);
@@ -92,6 +412,8 @@ impl LoadWrapper {
                         Some(AssetLoad {
                             id: self.id.clone(),
                             state: AssetState::Err(err),
+                            type_id: self.type_id,
+                            reload: self.reload,
                         })
                     }
                 }
@@ -104,3 +426,57 @@ impl LoadWrapper {
         self.loaded
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures::future::pending;
+
+    fn mounting_loader() -> AssetLoader {
+        AssetLoader {
+            loading: vec![],
+            file_loader: FileLoader::new().unwrap(),
+            bundles: vec![],
+            // loads are deferred while the bundle is mounting
+            mounting: vec![LoadWrapper::new(
+                "bundle.gkb",
+                None,
+                false,
+                Box::pin(pending()),
+            )],
+            deferred: vec![],
+            groups: HashMap::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            watcher: None,
+        }
+    }
+
+    fn deferred(loader: &AssetLoader) -> Vec<&str> {
+        loader
+            .deferred
+            .iter()
+            .map(|(path, _)| path.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_cancel_group() {
+        let mut loader = mounting_loader();
+        loader
+            .load_raw_in("menu", "shared.png")
+            .load_raw_in("menu", "menu.png")
+            .load_raw_in("game", "shared.png")
+            .load_raw("plain.png")
+            .load_raw_in("menu", "plain.png");
+
+        let group = loader.cancel_group("menu").unwrap();
+        assert_eq!(group.total(), 3);
+        assert!(loader.group("menu").is_none());
+        assert_eq!(deferred(&loader), vec!["shared.png", "plain.png"]);
+        assert_eq!(loader.group("game").unwrap().pending(), ["shared.png"]);
+
+        assert!(loader.cancel_group("game").is_some());
+        assert_eq!(deferred(&loader), vec!["plain.png"]);
+        assert!(loader.cancel_group("game").is_none());
+    }
+}
@@ -0,0 +1,75 @@
+/// Alphabet of the standard base64 encoding, '=' is used as padding
+const BASE64_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode the data as a base64 string to store binary data as text
+pub(super) fn encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    data.chunks(3).for_each(|chunk| {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        (0..4).for_each(|i| {
+            if i <= chunk.len() {
+                let idx = (n >> (18 - i * 6)) & 0x3f;
+                out.push(BASE64_CHARS[idx as usize] as char);
+            } else {
+                out.push('=');
+            }
+        });
+    });
+    out
+}
+
+pub(super) fn decode(text: &str) -> Result<Vec<u8>, String> {
+    let text = text.trim_end_matches('=');
+    let mut out = Vec::with_capacity(text.len() * 3 / 4);
+    let mut n = 0u32;
+    let mut bits = 0;
+    for c in text.bytes() {
+        let value = BASE64_CHARS
+            .iter()
+            .position(|&b| b == c)
+            .ok_or_else(|| "Invalid user data encoding".to_string())?;
+        n = n << 6 | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((n >> bits) as u8);
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode(b""), "");
+        assert_eq!(encode(b"f"), "Zg==");
+        assert_eq!(encode(b"fo"), "Zm8=");
+        assert_eq!(encode(b"foo"), "Zm9v");
+        assert_eq!(encode(b"foob"), "Zm9vYg==");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let data = (0..=255).collect::<Vec<u8>>();
+        // lengths with 0, 2 and 1 padding chars
+        (0..=6).chain([254, 255, 256]).for_each(|len| {
+            let encoded = encode(&data[..len]);
+            assert_eq!(encoded.len() % 4, 0);
+            assert_eq!(decode(&encoded).unwrap(), &data[..len]);
+        });
+    }
+
+    #[test]
+    fn test_invalid_decode() {
+        assert!(decode("Zm9v!").is_err());
+    }
+}
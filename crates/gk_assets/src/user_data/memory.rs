@@ -0,0 +1,30 @@
+use super::UserDataBackend;
+use hashbrown::HashMap;
+
+/// Keeps the files in memory, nothing is persisted. Useful for tests
+#[derive(Debug, Default, Clone)]
+pub struct MemoryBackend {
+    files: HashMap<String, Vec<u8>>,
+}
+
+impl UserDataBackend for MemoryBackend {
+    fn read(&self, path: &str) -> Result<Option<Vec<u8>>, String> {
+        Ok(self.files.get(path).cloned())
+    }
+
+    fn write(&mut self, path: &str, data: &[u8]) -> Result<(), String> {
+        self.files.insert(path.to_string(), data.to_vec());
+        Ok(())
+    }
+
+    fn remove(&mut self, path: &str) -> Result<(), String> {
+        self.files.remove(path);
+        Ok(())
+    }
+
+    fn list(&self) -> Result<Vec<String>, String> {
+        let mut paths = self.files.keys().cloned().collect::<Vec<_>>();
+        paths.sort();
+        Ok(paths)
+    }
+}
@@ -0,0 +1,232 @@
+#[cfg(any(target_arch = "wasm32", test))]
+mod base64;
+mod memory;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native;
+
+#[cfg(target_arch = "wasm32")]
+mod web;
+
+pub use memory::MemoryBackend;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use native::FileBackend;
+
+#[cfg(target_arch = "wasm32")]
+pub use web::LocalStorageBackend;
+
+use gk_sys::{AppBuilder, BuildConfig, GKState, Plugin};
+
+/// Where the user data is persisted
+pub trait UserDataBackend {
+    /// Returns `None` if the file doesn't exist
+    fn read(&self, path: &str) -> Result<Option<Vec<u8>>, String>;
+    fn write(&mut self, path: &str, data: &[u8]) -> Result<(), String>;
+    fn remove(&mut self, path: &str) -> Result<(), String>;
+    /// Paths of the stored files
+    fn list(&self) -> Result<Vec<String>, String>;
+}
+
+/// Persistent storage for save games, settings and other user files
+pub struct UserData {
+    backend: Box<dyn UserDataBackend>,
+}
+
+impl Plugin for UserData {}
+
+impl UserData {
+    pub fn config() -> UserDataConfig {
+        UserDataConfig::default()
+    }
+
+    pub fn new<B: UserDataBackend + 'static>(backend: B) -> Self {
+        Self {
+            backend: Box::new(backend),
+        }
+    }
+
+    /// Returns `None` if the file doesn't exist
+    pub fn read(&self, path: &str) -> Result<Option<Vec<u8>>, String> {
+        self.backend.read(&normalize(path)?)
+    }
+
+    pub fn read_string(&self, path: &str) -> Result<Option<String>, String> {
+        self.read(path)?
+            .map(|data| String::from_utf8(data).map_err(|e| e.to_string()))
+            .transpose()
+    }
+
+    pub fn write(&mut self, path: &str, data: &[u8]) -> Result<(), String> {
+        self.backend.write(&normalize(path)?, data)
+    }
+
+    pub fn write_string(&mut self, path: &str, text: &str) -> Result<(), String> {
+        self.write(path, text.as_bytes())
+    }
+
+    pub fn remove(&mut self, path: &str) -> Result<(), String> {
+        self.backend.remove(&normalize(path)?)
+    }
+
+    pub fn exists(&self, path: &str) -> Result<bool, String> {
+        Ok(self.read(path)?.is_some())
+    }
+
+    /// Paths of the stored files
+    pub fn list(&self) -> Result<Vec<String>, String> {
+        self.backend.list()
+    }
+
+    /// Deserialize the JSON file, returns `None` if it doesn't exist
+    #[cfg(feature = "serde")]
+    pub fn read_json<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<Option<T>, String> {
+        self.read(path)?
+            .map(|data| serde_json::from_slice(&data).map_err(|e| e.to_string()))
+            .transpose()
+    }
+
+    /// Serialize the value as JSON into the file
+    #[cfg(feature = "serde")]
+    pub fn write_json<T: serde::Serialize>(&mut self, path: &str, value: &T) -> Result<(), String> {
+        let data = serde_json::to_vec_pretty(value).map_err(|e| e.to_string())?;
+        self.write(path, &data)
+    }
+}
+
+#[derive(Default)]
+pub struct UserDataConfig {
+    name: Option<String>,
+    backend: Option<Box<dyn UserDataBackend>>,
+}
+
+impl UserDataConfig {
+    /// Name of the app's folder (native) or key prefix (web), by default the executable name
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Use a custom backend instead of the platform's one
+    pub fn with_backend<B: UserDataBackend + 'static>(mut self, backend: B) -> Self {
+        self.backend = Some(Box::new(backend));
+        self
+    }
+}
+
+impl<S: GKState + 'static> BuildConfig<S> for UserDataConfig {
+    fn apply(&mut self, builder: AppBuilder<S>) -> Result<AppBuilder<S>, String> {
+        let backend = match self.backend.take() {
+            Some(backend) => backend,
+            None => platform_backend(&app_name(self.name.as_deref()))?,
+        };
+
+        Ok(builder.add_plugin(UserData { backend }))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn platform_backend(name: &str) -> Result<Box<dyn UserDataBackend>, String> {
+    Ok(Box::new(FileBackend::new(native::config_dir()?.join(name))))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn platform_backend(name: &str) -> Result<Box<dyn UserDataBackend>, String> {
+    Ok(Box::new(LocalStorageBackend::new(name)?))
+}
+
+fn app_name(name: Option<&str>) -> String {
+    name.map(str::to_string)
+        .or_else(|| {
+            std::env::current_exe()
+                .ok()?
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+        })
+        .unwrap_or_else(|| "gamekit".to_string())
+}
+
+/// Suffix of the files written before replacing the user data, reserved so no path can use it
+const TMP_SUFFIX: &str = ".~gk_tmp";
+
+/// Relative path using '/' as separator, parent or absolute paths are not allowed
+fn normalize(path: &str) -> Result<String, String> {
+    let parts = path
+        .split(['/', '\\'])
+        .filter(|part| !part.is_empty() && *part != ".")
+        .collect::<Vec<_>>();
+
+    if parts.is_empty() {
+        return Err(format!("Invalid user data path '{}'", path));
+    }
+
+    if parts.iter().any(|part| *part == ".." || part.contains(':')) {
+        return Err(format!(
+            "User data path '{}' cannot point outside its folder",
+            path
+        ));
+    }
+
+    if parts.iter().any(|part| part.ends_with(TMP_SUFFIX)) {
+        return Err(format!(
+            "User data path '{}' cannot end with '{}'",
+            path, TMP_SUFFIX
+        ));
+    }
+
+    Ok(parts.join("/"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("./saves//slot1.sav").unwrap(), "saves/slot1.sav");
+        assert_eq!(normalize("\\saves\\slot1.sav").unwrap(), "saves/slot1.sav");
+        assert!(normalize("../slot1.sav").is_err());
+        assert!(normalize("C:/slot1.sav").is_err());
+        assert!(normalize("./").is_err());
+        assert!(normalize("settings.json.~gk_tmp").is_err());
+        assert!(normalize("saves.~gk_tmp/slot1.sav").is_err());
+    }
+
+    #[test]
+    fn test_user_data() {
+        let mut data = UserData::new(MemoryBackend::default());
+        assert_eq!(data.read("saves/slot1.sav").unwrap(), None);
+
+        data.write_string("./saves/slot1.sav", "level 2").unwrap();
+        assert!(data.exists("saves/slot1.sav").unwrap());
+        assert_eq!(
+            data.read_string("saves/slot1.sav").unwrap().as_deref(),
+            Some("level 2")
+        );
+        assert_eq!(data.list().unwrap(), vec!["saves/slot1.sav".to_string()]);
+
+        data.remove("saves/slot1.sav").unwrap();
+        assert!(!data.exists("saves/slot1.sav").unwrap());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_user_data_json() {
+        #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+        struct Save {
+            level: u32,
+        }
+
+        let mut data = UserData::new(MemoryBackend::default());
+        assert_eq!(data.read_json::<Save>("save.json").unwrap(), None);
+
+        data.write_json("save.json", &Save { level: 2 }).unwrap();
+        assert_eq!(
+            data.read_json::<Save>("save.json").unwrap(),
+            Some(Save { level: 2 })
+        );
+    }
+}
@@ -0,0 +1,152 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use super::{UserDataBackend, TMP_SUFFIX};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// Stores the files inside a folder, by default under the platform's config dir
+#[derive(Debug, Clone)]
+pub struct FileBackend {
+    root: PathBuf,
+}
+
+impl FileBackend {
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Folder where the files are stored
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl UserDataBackend for FileBackend {
+    fn read(&self, path: &str) -> Result<Option<Vec<u8>>, String> {
+        match std::fs::read(self.root.join(path)) {
+            Ok(data) => Ok(Some(data)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(format!("Cannot read user data '{}': {}", path, err)),
+        }
+    }
+
+    fn write(&mut self, path: &str, data: &[u8]) -> Result<(), String> {
+        let file_path = self.root.join(path);
+        if let Some(parent) = file_path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Cannot create user data dir '{:?}': {}", parent, e))?;
+        }
+
+        // write to a temporary file first to avoid corrupted files if the app is closed
+        let mut tmp_path = file_path.clone().into_os_string();
+        tmp_path.push(TMP_SUFFIX);
+        std::fs::write(&tmp_path, data)
+            .and_then(|_| std::fs::rename(&tmp_path, &file_path))
+            .map_err(|e| format!("Cannot write user data '{}': {}", path, e))
+    }
+
+    fn remove(&mut self, path: &str) -> Result<(), String> {
+        match std::fs::remove_file(self.root.join(path)) {
+            Ok(_) => Ok(()),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(format!("Cannot remove user data '{}': {}", path, err)),
+        }
+    }
+
+    fn list(&self) -> Result<Vec<String>, String> {
+        let mut paths = vec![];
+        if self.root.exists() {
+            list_dir(&self.root, &self.root, &mut paths)?;
+        }
+        paths.sort();
+        Ok(paths)
+    }
+}
+
+fn list_dir(root: &Path, dir: &Path, paths: &mut Vec<String>) -> Result<(), String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| e.to_string())?;
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        // temporary files are left only if the app was closed while writing
+        let is_tmp = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().ends_with(TMP_SUFFIX));
+        if path.is_dir() {
+            list_dir(root, &path, paths)?;
+        } else if is_tmp {
+            continue;
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            let parts = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>();
+            paths.push(parts.join("/"));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+pub(super) fn config_dir() -> Result<PathBuf, String> {
+    std::env::var_os("APPDATA")
+        .map(PathBuf::from)
+        .ok_or_else(|| "Cannot find the user config dir: APPDATA is not set".to_string())
+}
+
+#[cfg(target_os = "macos")]
+pub(super) fn config_dir() -> Result<PathBuf, String> {
+    home_dir().map(|home| home.join("Library/Application Support"))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub(super) fn config_dir() -> Result<PathBuf, String> {
+    match std::env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+        Some(dir) => Ok(PathBuf::from(dir)),
+        None => home_dir().map(|home| home.join(".config")),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn home_dir() -> Result<PathBuf, String> {
+    std::env::var_os("HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .ok_or_else(|| "Cannot find the user config dir: HOME is not set".to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_file_backend() {
+        let root = std::env::temp_dir().join(format!("gk_user_data_{}", std::process::id()));
+        let mut backend = FileBackend::new(&root);
+        assert_eq!(backend.read("saves/slot1.sav").unwrap(), None);
+
+        backend.write("saves/slot1.sav", b"level 2").unwrap();
+        backend.write("settings.json", b"{}").unwrap();
+        backend.write("notes.tmp", b"user file").unwrap();
+        std::fs::write(root.join("settings.json.~gk_tmp"), b"{").unwrap();
+        assert_eq!(
+            backend.read("saves/slot1.sav").unwrap().as_deref(),
+            Some(b"level 2".as_slice())
+        );
+        assert_eq!(
+            backend.list().unwrap(),
+            vec![
+                "notes.tmp".to_string(),
+                "saves/slot1.sav".to_string(),
+                "settings.json".to_string()
+            ]
+        );
+
+        backend.remove("saves/slot1.sav").unwrap();
+        assert_eq!(backend.read("saves/slot1.sav").unwrap(), None);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,63 @@
+#![cfg(target_arch = "wasm32")]
+
+use super::base64::{decode, encode};
+use super::UserDataBackend;
+use web_sys::Storage;
+
+/// Stores the files in the browser's `localStorage` as base64 strings
+pub struct LocalStorageBackend {
+    prefix: String,
+    storage: Storage,
+}
+
+impl LocalStorageBackend {
+    /// Keys are prefixed by `name` to avoid collisions with other apps on the same domain
+    pub fn new(name: &str) -> Result<Self, String> {
+        let storage = web_sys::window()
+            .ok_or_else(|| "Cannot access the browser's window".to_string())?
+            .local_storage()
+            .map_err(|e| format!("{:?}", e))?
+            .ok_or_else(|| "localStorage is not available".to_string())?;
+
+        Ok(Self {
+            prefix: format!("{}/", name),
+            storage,
+        })
+    }
+
+    fn key(&self, path: &str) -> String {
+        format!("{}{}", self.prefix, path)
+    }
+}
+
+impl UserDataBackend for LocalStorageBackend {
+    fn read(&self, path: &str) -> Result<Option<Vec<u8>>, String> {
+        self.storage
+            .get_item(&self.key(path))
+            .map_err(|e| format!("Cannot read user data '{}': {:?}", path, e))?
+            .map(|encoded| decode(&encoded))
+            .transpose()
+    }
+
+    fn write(&mut self, path: &str, data: &[u8]) -> Result<(), String> {
+        self.storage
+            .set_item(&self.key(path), &encode(data))
+            .map_err(|e| format!("Cannot write user data '{}': {:?}", path, e))
+    }
+
+    fn remove(&mut self, path: &str) -> Result<(), String> {
+        self.storage
+            .remove_item(&self.key(path))
+            .map_err(|e| format!("Cannot remove user data '{}': {:?}", path, e))
+    }
+
+    fn list(&self) -> Result<Vec<String>, String> {
+        let len = self.storage.length().map_err(|e| format!("{:?}", e))?;
+        let mut paths = (0..len)
+            .filter_map(|i| self.storage.key(i).ok().flatten())
+            .filter_map(|key| key.strip_prefix(&self.prefix).map(str::to_string))
+            .collect::<Vec<_>>();
+        paths.sort();
+        Ok(paths)
+    }
+}
@@ -0,0 +1,137 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use hashbrown::HashMap;
+use std::any::TypeId;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Watch loaded files polling their modification time
+pub(crate) struct FileWatcher {
+    files: HashMap<WatchKey, Option<SystemTime>>,
+    loading: HashMap<WatchKey, Option<SystemTime>>,
+    interval: Duration,
+    last_check: Instant,
+}
+
+/// The same file can be loaded as different asset types
+type WatchKey = (String, Option<TypeId>);
+
+impl FileWatcher {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            files: HashMap::default(),
+            loading: HashMap::default(),
+            interval,
+            last_check: Instant::now(),
+        }
+    }
+
+    /// Keep the modification time before the file is read,
+    /// so changes made while it's loading are detected on the next check
+    pub fn prepare(&mut self, path: &str, type_id: Option<TypeId>) {
+        self.loading
+            .insert((path.to_string(), type_id), modified_time(path));
+    }
+
+    /// Start watching the file using the modification time taken by `prepare`,
+    /// if it's already watched it updates the modification time
+    pub fn watch(&mut self, path: &str, type_id: Option<TypeId>) {
+        let key = (path.to_string(), type_id);
+        let modified = self
+            .loading
+            .remove(&key)
+            .unwrap_or_else(|| modified_time(path));
+        self.files.insert(key, modified);
+    }
+
+    /// The file failed to load, the last modification time watched is kept
+    pub fn discard(&mut self, path: &str, type_id: Option<TypeId>) {
+        self.loading.remove(&(path.to_string(), type_id));
+    }
+
+    /// Returns the files modified since the last check
+    pub fn changed(&mut self) -> Vec<WatchKey> {
+        if self.last_check.elapsed() < self.interval {
+            return vec![];
+        }
+
+        self.last_check = Instant::now();
+        self.files
+            .iter_mut()
+            .filter_map(|(key, last_modified)| {
+                let modified = modified_time(&key.0);
+                // files removed while saving (some editors do it) are skipped until they exist again
+                if modified.is_none() || modified == *last_modified {
+                    return None;
+                }
+
+                *last_modified = modified;
+                Some(key.clone())
+            })
+            .collect()
+    }
+}
+
+fn modified_time(path: &str) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_file(name: &str) -> (PathBuf, String) {
+        let nanos = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let file_name = format!("gk_assets_{}_{}_{}.txt", name, std::process::id(), nanos);
+        let path = std::env::temp_dir().join(file_name);
+        std::fs::write(&path, "a").unwrap();
+        let path_str = path.to_string_lossy().to_string();
+        (path, path_str)
+    }
+
+    fn touch(path: &PathBuf, secs: u64) {
+        let file = std::fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(secs))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_file_changed() {
+        let (path, path_str) = temp_file("watcher_changed");
+
+        let mut watcher = FileWatcher::new(Duration::ZERO);
+        watcher.watch(&path_str, None);
+        assert!(watcher.changed().is_empty());
+
+        touch(&path, 10);
+
+        let changed = watcher.changed();
+        assert_eq!(changed, vec![(path_str, None)]);
+        assert!(watcher.changed().is_empty());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_file_changed_while_loading() {
+        let (path, path_str) = temp_file("watcher_loading");
+        let type_id = Some(TypeId::of::<String>());
+
+        let mut watcher = FileWatcher::new(Duration::ZERO);
+        watcher.prepare(&path_str, type_id);
+        touch(&path, 10);
+        watcher.watch(&path_str, type_id);
+
+        // the same file loaded as raw bytes is watched too
+        watcher.watch(&path_str, None);
+
+        let changed = watcher.changed();
+        assert_eq!(changed, vec![(path_str, type_id)]);
+        assert!(watcher.changed().is_empty());
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
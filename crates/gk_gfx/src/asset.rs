@@ -0,0 +1,11 @@
+use crate::{Gfx, Texture};
+use gk_assets::{Asset, AssetParsers};
+
+impl Asset for Texture {
+    fn parsers() -> AssetParsers<Self> {
+        AssetParsers::default().add_using(
+            &["png", "jpg", "jpeg", "ktx2", "dds"],
+            |id, data, gfx: &mut Gfx| gfx.create_texture().with_label(id).from_image(data).build(),
+        )
+    }
+}
@@ -1,3 +1,4 @@
+use crate::consts::SURFACE_DEFAULT_DEPTH_FORMAT;
 use crate::TextureFormat;
 
 /// Configuration to use with the GFX system
@@ -7,6 +8,10 @@ pub struct GfxAttributes {
     pub vsync: bool,
     /// This format will be used to create depth textures
     pub depth_format: TextureFormat,
+    /// Create the device without surfaces, it can only render to RenderTextures
+    pub headless: bool,
+    /// MSAA samples used by the surfaces, 1 disables it
+    pub msaa_samples: u32,
     // TODO wgpu backends?
 }
 
@@ -14,7 +19,9 @@ impl Default for GfxAttributes {
     fn default() -> Self {
         Self {
             vsync: false,
-            depth_format: TextureFormat::Depth32Float,
+            depth_format: SURFACE_DEFAULT_DEPTH_FORMAT,
+            headless: false,
+            msaa_samples: 1,
         }
     }
 }
@@ -1,5 +1,5 @@
 use crate::consts::{MAX_SAMPLED_TEXTURES_PER_SHADER_STAGE, MAX_UNIFORM_BUFFERS_PER_SHADER_STAGE};
-use crate::{BindGroupLayoutRef, Buffer, Sampler, Texture};
+use crate::{BindGroupLayoutRef, Buffer, GKBuffer, Sampler, Texture, TextureFormat};
 use arrayvec::ArrayVec;
 use gk_macro::ResourceId;
 
@@ -45,13 +45,27 @@ impl BindGroupLayout {
         self.entries.push(binding);
         self
     }
+
+    /// Locations of the uniforms using dynamic offsets, in the order the offsets are passed
+    pub(crate) fn dynamic_uniforms(&self) -> Vec<u32> {
+        let mut locations = self
+            .entries
+            .iter()
+            .filter(|entry| entry.dynamic_offset)
+            .map(|entry| entry.location)
+            .collect::<Vec<_>>();
+        locations.sort();
+        locations
+    }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub(crate) enum BindType {
     Texture,
     Sampler,
     Uniform,
+    StorageBuffer { read_only: bool },
+    StorageTexture(TextureFormat),
 }
 
 #[derive(Copy, Clone)]
@@ -61,6 +75,8 @@ pub struct BindingType {
     pub(crate) visible_fragment: bool,
     pub(crate) visible_vertex: bool,
     pub(crate) visible_compute: bool,
+    pub(crate) dynamic_offset: bool,
+    pub(crate) filterable: bool,
 }
 
 impl BindingType {
@@ -71,6 +87,8 @@ impl BindingType {
             visible_fragment: false,
             visible_vertex: false,
             visible_compute: false,
+            dynamic_offset: false,
+            filterable: true,
         }
     }
 
@@ -81,6 +99,8 @@ impl BindingType {
             visible_fragment: false,
             visible_vertex: false,
             visible_compute: false,
+            dynamic_offset: false,
+            filterable: true,
         }
     }
 
@@ -91,9 +111,74 @@ impl BindingType {
             visible_fragment: false,
             visible_vertex: false,
             visible_compute: false,
+            dynamic_offset: false,
+            filterable: true,
+        }
+    }
+
+    /// Read-write storage buffer, vertex shaders can only use read-only ones
+    pub fn storage_buffer(location: u32) -> Self {
+        Self {
+            location,
+            typ: BindType::StorageBuffer { read_only: false },
+            visible_fragment: false,
+            visible_vertex: false,
+            visible_compute: false,
+            dynamic_offset: false,
+            filterable: true,
         }
     }
 
+    /// Write-only storage texture
+    pub fn storage_texture(location: u32, format: TextureFormat) -> Self {
+        Self {
+            location,
+            typ: BindType::StorageTexture(format),
+            visible_fragment: false,
+            visible_vertex: false,
+            visible_compute: false,
+            dynamic_offset: false,
+            filterable: true,
+        }
+    }
+
+    /// Only for storage buffers
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        debug_assert!(
+            matches!(self.typ, BindType::StorageBuffer { .. }),
+            "Only storage buffers can be read-only"
+        );
+        if let BindType::StorageBuffer { .. } = self.typ {
+            self.typ = BindType::StorageBuffer { read_only };
+        }
+        self
+    }
+
+    /// Only for uniforms, the offset is set with `RenderPass::dynamic_offsets`
+    pub fn with_dynamic_offset(mut self, dynamic: bool) -> Self {
+        debug_assert!(
+            matches!(self.typ, BindType::Uniform),
+            "Only uniforms can use dynamic offsets"
+        );
+        if let BindType::Uniform = self.typ {
+            self.dynamic_offset = dynamic;
+        }
+        self
+    }
+
+    /// Only for textures, non-filterable formats like `Rgba32Float` or depth
+    /// need it disabled and can only be read with `textureLoad`
+    pub fn with_filterable(mut self, filterable: bool) -> Self {
+        debug_assert!(
+            matches!(self.typ, BindType::Texture),
+            "Only textures can be filterable"
+        );
+        if let BindType::Texture = self.typ {
+            self.filterable = filterable;
+        }
+        self
+    }
+
     pub fn with_fragment_visibility(mut self, visible: bool) -> Self {
         self.visible_fragment = visible;
         self
@@ -117,9 +202,66 @@ pub struct BindGroupDescriptor<'a> {
     pub entry: ArrayVec<BindGroupEntry<'a>, MAX_BINDING_ENTRIES>,
 }
 
+impl BindGroupDescriptor<'_> {
+    /// Bound sizes of the uniforms using dynamic offsets in the layout
+    pub(crate) fn dynamic_bindings(&self) -> Result<Vec<DynamicBinding>, String> {
+        let Some(layout) = self.layout else {
+            return Ok(vec![]);
+        };
+
+        layout
+            .dynamic_uniforms
+            .iter()
+            .map(|loc| {
+                let found = self.entry.iter().find_map(|entry| match entry {
+                    BindGroupEntry::Uniform {
+                        location,
+                        buffer,
+                        size,
+                    } if location == loc => {
+                        let buffer_size = buffer.len() as u64;
+                        Some(DynamicBinding {
+                            size: size.unwrap_or(buffer_size),
+                            buffer_size,
+                        })
+                    }
+                    _ => None,
+                });
+                found
+                    .ok_or_else(|| format!("Dynamic uniform '{}' is missing in the BindGroup", loc))
+            })
+            .collect()
+    }
+}
+
+/// Uniform bound with a dynamic offset, `offset + size` must fit in the buffer
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct DynamicBinding {
+    pub(crate) size: u64,
+    pub(crate) buffer_size: u64,
+}
+
 #[derive(Copy, Clone)]
 pub enum BindGroupEntry<'a> {
-    Texture { location: u32, texture: &'a Texture },
-    Sampler { location: u32, sampler: &'a Sampler },
-    Uniform { location: u32, buffer: &'a Buffer },
+    Texture {
+        location: u32,
+        texture: &'a Texture,
+    },
+    Sampler {
+        location: u32,
+        sampler: &'a Sampler,
+    },
+    Uniform {
+        location: u32,
+        buffer: &'a Buffer,
+        size: Option<u64>,
+    },
+    StorageBuffer {
+        location: u32,
+        buffer: &'a Buffer,
+    },
+    StorageTexture {
+        location: u32,
+        texture: &'a Texture,
+    },
 }
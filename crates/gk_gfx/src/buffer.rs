@@ -27,6 +27,8 @@ pub enum BufferUsage {
     Vertex,
     Index,
     Uniform,
+    /// Read and written by shaders, it can be used as vertex buffer too
+    Storage,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -103,9 +105,19 @@ pub enum VertexFormat {
     Int32x4,
 }
 
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 pub enum IndexFormat {
     UInt16,
     #[default]
     UInt32,
 }
+
+impl IndexFormat {
+    /// Size in bytes of each index
+    pub fn bytes(&self) -> u64 {
+        match self {
+            IndexFormat::UInt16 => 2,
+            IndexFormat::UInt32 => 4,
+        }
+    }
+}
@@ -1,12 +1,15 @@
 use crate::gfx::Gfx;
+use crate::recorder::FrameRecorder;
 use crate::GfxAttributes;
 use gk_app::App;
+use gk_sys::event;
 use gk_sys::window::{WindowAction, WindowEvent};
 use gk_sys::{AppBuilder, BuildConfig, EventQueue, GKState};
 
 #[derive(Default)]
 pub struct GfxConfig {
     attrs: GfxAttributes,
+    recorder: Option<FrameRecorder>,
 }
 
 impl GfxConfig {
@@ -19,15 +22,47 @@ impl GfxConfig {
         self.attrs.vsync = enable;
         self
     }
+
+    /// Save the first `frames` presented frames as PNG images inside `dir`
+    /// Only frames passed to `Gfx::present` are captured, RenderTextures are never recorded
+    pub fn with_frame_recording(mut self, dir: &str, frames: usize) -> Self {
+        self.recorder = Some(FrameRecorder::new(dir, frames));
+        self
+    }
+
+    /// Antialias the surfaces using MSAA with the given samples (1 or 4)
+    pub fn with_msaa(mut self, samples: u32) -> Self {
+        self.attrs.msaa_samples = samples;
+        self
+    }
+
+    /// Initialize the device without any window surface
+    /// Only RenderTextures can be used as render target in this mode
+    pub fn with_headless(mut self, enable: bool) -> Self {
+        self.attrs.headless = enable;
+        self
+    }
 }
 
 impl<S: GKState + 'static> BuildConfig<S> for GfxConfig {
     fn apply(&mut self, builder: AppBuilder<S>) -> Result<AppBuilder<S>, String> {
-        let builder = builder.on(on_window_event);
-
         let attrs = self.attrs;
+        let recorder = self.recorder.take();
+
+        // the frame recorder moves to the next frame when the current one ends
+        let builder = builder.on(|_: &event::FrameEndEvent, gfx: &mut Gfx| gfx.frame_end());
+
+        // headless mode doesn't depend on the platform or windows
+        if attrs.headless {
+            let mut gfx = Gfx::new(attrs)?;
+            gfx.recorder = recorder;
+            return Ok(builder.add_plugin(gfx));
+        }
+
+        let builder = builder.on(on_window_event);
         builder.add_plugin_with(move |platform: &mut App| {
             let mut gfx = Gfx::new(attrs)?;
+            gfx.recorder = recorder;
             if let Some(win) = platform.main_window() {
                 gfx.init_surface(win)?;
             }
@@ -2,13 +2,25 @@
 
 use crate::TextureFormat;
 
-pub const SURFACE_DEFAULT_DEPTH_FORMAT: TextureFormat = TextureFormat::Depth32Float;
+pub const SURFACE_DEFAULT_DEPTH_FORMAT: TextureFormat = TextureFormat::Depth24PlusStencil8;
+
+pub const DEFAULT_VERTEX_ENTRY: &str = "vs_main";
+pub const DEFAULT_FRAGMENT_ENTRY: &str = "fs_main";
+pub const DEFAULT_COMPUTE_ENTRY: &str = "cs_main";
 
 pub const MAX_VERTEX_BUFFERS: usize = 8;
 pub const MAX_VERTEX_ATTRIBUTES: usize = 16;
 pub const MAX_SAMPLERS_PER_SHADER_STAGE: usize = 16;
 pub const MAX_SAMPLED_TEXTURES_PER_SHADER_STAGE: usize = 16;
 pub const MAX_BIND_GROUPS_PER_PIPELINE: usize = 4;
+pub const MAX_DYNAMIC_UNIFORM_BUFFERS_PER_PIPELINE: usize = 8;
+pub const UNIFORM_BUFFER_OFFSET_ALIGNMENT: u32 = 256;
+
+#[cfg(all(target_arch = "wasm32", feature = "webgl"))]
+pub const MAX_TEXTURE_SIZE: u32 = 2048;
+
+#[cfg(not(all(target_arch = "wasm32", feature = "webgl")))]
+pub const MAX_TEXTURE_SIZE: u32 = 8192;
 
 #[cfg(all(target_arch = "wasm32", feature = "webgl"))]
 pub const MAX_UNIFORM_BUFFERS_PER_SHADER_STAGE: usize = 11;
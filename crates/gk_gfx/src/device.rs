@@ -1,11 +1,16 @@
 use crate::attrs::GfxAttributes;
 use crate::buffer::{BufferDescriptor, GKBuffer};
 use crate::frame::GKDrawFrame;
-use crate::pipeline::{GKRenderPipeline, RenderPipelineDescriptor};
+use crate::pipeline::{
+    ComputePipelineDescriptor, GKComputePipeline, GKRenderPipeline, RenderPipelineDescriptor,
+};
 use crate::render_target::RenderTarget;
 use crate::render_texture::{GKRenderTexture, RenderTextureDescriptor};
 use crate::renderer::Renderer;
-use crate::texture::{GKSampler, GKTexture, SamplerDescriptor, TextureData, TextureDescriptor};
+use crate::texture::{
+    GKSampler, GKTexture, SamplerDescriptor, TextureData, TextureDescriptor, TextureFormat,
+    TextureRect,
+};
 use crate::{BindGroupDescriptor, DrawFrame, GKBindGroup, GKBindGroupLayoutRef};
 use gk_sys::window::{GKWindow, WindowId};
 
@@ -18,6 +23,7 @@ pub trait GKDevice<
     BG: GKBindGroup,
     BGL: GKBindGroupLayoutRef,
     RT: GKRenderTexture,
+    CP: GKComputePipeline,
 >
 {
     fn new(attrs: GfxAttributes) -> Result<Self, String>
@@ -27,14 +33,19 @@ pub trait GKDevice<
     fn present(&mut self, frame: DF) -> Result<(), String>;
     fn init_surface<W: GKWindow>(&mut self, win: &W) -> Result<(), String>;
     fn create_render_pipeline(&mut self, desc: RenderPipelineDescriptor) -> Result<RP, String>;
+    fn create_compute_pipeline(&mut self, desc: ComputePipelineDescriptor) -> Result<CP, String>;
     fn create_buffer(&mut self, desc: BufferDescriptor) -> Result<B, String>;
     fn create_render_texture(&mut self, desc: RenderTextureDescriptor) -> Result<RT, String>;
+    fn is_format_supported(&self, format: TextureFormat) -> bool;
     fn create_texture(
         &mut self,
         desc: TextureDescriptor,
         data: Option<TextureData>,
     ) -> Result<T, String>;
     fn write_buffer(&mut self, buffer: &B, offset: u64, data: &[u8]) -> Result<(), String>;
+    fn write_texture(&mut self, texture: &T, rect: TextureRect, data: &[u8]) -> Result<(), String>;
+    fn read_pixels(&mut self, texture: &T, rect: TextureRect) -> Result<Vec<u8>, String>;
+    fn read_frame_pixels(&mut self, frame: &DF, rect: TextureRect) -> Result<Vec<u8>, String>;
     fn create_sampler(&mut self, desc: SamplerDescriptor) -> Result<S, String>;
     fn create_bind_group(&mut self, desc: BindGroupDescriptor) -> Result<BG, String>;
     fn resize(&mut self, id: WindowId, width: u32, height: u32) -> Result<(), String>;
@@ -43,4 +54,7 @@ pub trait GKDevice<
     fn render_to_frame(&mut self, frame: &DF, renderer: &Renderer) -> Result<(), String>;
 
     fn render_to_texture(&mut self, frame: &RT, renderer: &Renderer) -> Result<(), String>;
+
+    /// Execute the compute passes without a render target
+    fn compute(&mut self, renderer: &Renderer) -> Result<(), String>;
 }
@@ -0,0 +1,27 @@
+use crate::bind_group::DynamicBinding;
+use crate::{BindGroupId, BindGroupLayoutId, GKBindGroup, GKBindGroupLayoutRef};
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct BindGroup {
+    pub(crate) id: BindGroupId,
+    pub(crate) dynamic_bindings: Arc<[DynamicBinding]>,
+}
+
+impl GKBindGroup for BindGroup {
+    fn id(&self) -> BindGroupId {
+        self.id
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BindGroupLayoutRef {
+    pub(crate) id: BindGroupLayoutId,
+    pub(crate) dynamic_uniforms: Arc<[u32]>,
+}
+
+impl GKBindGroupLayoutRef for BindGroupLayoutRef {
+    fn id(&self) -> BindGroupLayoutId {
+        self.id
+    }
+}
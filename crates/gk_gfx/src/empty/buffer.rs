@@ -1,4 +1,31 @@
-use crate::GKBuffer;
+use crate::buffer::{BufferId, BufferUsage, GKBuffer};
 
-pub struct Buffer;
-impl GKBuffer for Buffer {}
+#[derive(Debug, Clone)]
+pub struct Buffer {
+    pub(crate) id: BufferId,
+    pub(crate) usage: BufferUsage,
+    pub(crate) write: bool,
+    pub(crate) size: usize,
+}
+
+impl GKBuffer for Buffer {
+    fn id(&self) -> BufferId {
+        self.id
+    }
+
+    fn usage(&self) -> BufferUsage {
+        self.usage
+    }
+
+    fn is_writable(&self) -> bool {
+        self.write
+    }
+
+    fn len(&self) -> usize {
+        self.size
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
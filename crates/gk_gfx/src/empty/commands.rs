@@ -0,0 +1,110 @@
+use crate::render_texture::RenderTextureId;
+use crate::renderer::{ComputePass, RenderPass, Viewport};
+use crate::{
+    BindGroupId, BufferId, ClearOptions, GKBindGroup, GKBuffer, GKComputePipeline,
+    GKRenderPipeline, PipelineId, TextureRect,
+};
+use gk_sys::window::WindowId;
+use std::ops::Range;
+
+/// Target where a recorded pass was rendered
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RecordedTarget {
+    Frame(WindowId),
+    Texture(RenderTextureId),
+}
+
+/// Buffer binding recorded from a RenderPass
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RecordedBuffer {
+    pub id: BufferId,
+    pub offset: u64,
+    pub size: Option<u64>,
+}
+
+/// Draw call recorded from a RenderPass
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedDraw {
+    pub range: Range<u32>,
+    pub base_vertex: i32,
+    pub instances: Option<u32>,
+    pub viewport: Option<Viewport>,
+    pub scissor: Option<TextureRect>,
+    /// Dynamic offsets of each bound group
+    pub dynamic_offsets: Vec<Vec<u32>>,
+}
+
+/// A copy of the RenderPass sent to the device
+#[derive(Debug, Clone)]
+pub struct RecordedPass {
+    pub target: RecordedTarget,
+    pub size: Option<(u32, u32)>,
+    pub pipeline: Option<PipelineId>,
+    pub buffers: Vec<RecordedBuffer>,
+    pub bind_groups: Vec<BindGroupId>,
+    pub clear_options: ClearOptions,
+    pub stencil_ref: Option<u8>,
+    pub draws: Vec<RecordedDraw>,
+}
+
+impl RecordedPass {
+    pub(crate) fn new(target: RecordedTarget, rpass: &RenderPass) -> Self {
+        let mut dynamic_offsets = vec![vec![]; rpass.bind_groups.len()];
+        Self {
+            target,
+            size: rpass.size,
+            pipeline: rpass.pipeline.map(|pip| pip.id()),
+            buffers: rpass
+                .buffers
+                .iter()
+                .map(|rb| RecordedBuffer {
+                    id: rb.buffer.id(),
+                    offset: rb.offset,
+                    size: rb.size,
+                })
+                .collect(),
+            bind_groups: rpass.bind_groups.iter().map(|bg| bg.id()).collect(),
+            clear_options: rpass.clear_options,
+            stencil_ref: rpass.stencil_ref,
+            draws: rpass
+                .vertices
+                .iter()
+                .enumerate()
+                .map(|(i, vertices)| {
+                    rpass.offset_changes_at(i).iter().for_each(|change| {
+                        if let Some(offsets) = dynamic_offsets.get_mut(change.group) {
+                            *offsets = rpass.offsets[change.range.clone()].to_vec();
+                        }
+                    });
+
+                    RecordedDraw {
+                        range: vertices.range.clone(),
+                        base_vertex: vertices.base_vertex,
+                        instances: vertices.instances,
+                        viewport: vertices.viewport,
+                        scissor: vertices.scissor,
+                        dynamic_offsets: dynamic_offsets.clone(),
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A copy of the ComputePass sent to the device
+#[derive(Debug, Clone)]
+pub struct RecordedComputePass {
+    pub pipeline: Option<PipelineId>,
+    pub bind_groups: Vec<BindGroupId>,
+    pub dispatches: Vec<(u32, u32, u32)>,
+}
+
+impl RecordedComputePass {
+    pub(crate) fn new(cpass: &ComputePass) -> Self {
+        Self {
+            pipeline: cpass.pipeline.map(|pip| pip.id()),
+            bind_groups: cpass.bind_groups.iter().map(|bg| bg.id()).collect(),
+            dispatches: cpass.dispatches.clone(),
+        }
+    }
+}
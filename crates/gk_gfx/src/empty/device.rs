@@ -1,30 +1,469 @@
-use super::pipeline::Pipeline;
-use crate::renderer::Renderer;
-use crate::{GKDevice, RenderPipelineDescriptor};
+use super::commands::{RecordedComputePass, RecordedPass, RecordedTarget};
+use crate::attrs::GfxAttributes;
+use crate::buffer::{BufferDescriptor, GKBuffer};
+use crate::device::GKDevice;
+use crate::gfx::Gfx;
+use crate::pipeline::{ComputePipelineDescriptor, RenderPipelineDescriptor};
+use crate::render_texture::{GKRenderTexture, RenderTextureDescriptor};
+use crate::renderer::{Pass, Renderer};
+use crate::texture::{SamplerDescriptor, TextureData, TextureDescriptor, TextureRect};
+use crate::{
+    BindGroup, BindGroupDescriptor, BindGroupLayoutRef, Buffer, ComputePipeline, DrawFrame,
+    RenderPipeline, RenderTexture, Sampler, Texture, TextureFormat,
+};
 use gk_sys::window::{GKWindow, WindowId};
+use hashbrown::HashMap;
 
-pub struct Device;
+/// Device that doesn't render anything, but it keeps a copy of
+/// every pass that it receives, useful to test the render logic
+pub struct Device {
+    next_resource_id: u64,
+    attrs: GfxAttributes,
+    surfaces: HashMap<WindowId, (u32, u32)>,
+    passes: Vec<RecordedPass>,
+    compute_passes: Vec<RecordedComputePass>,
+}
+
+impl Device {
+    /// Passes rendered since the last clean
+    pub fn recorded_passes(&self) -> &[RecordedPass] {
+        &self.passes
+    }
+
+    /// Compute passes executed since the last clean
+    pub fn recorded_compute_passes(&self) -> &[RecordedComputePass] {
+        &self.compute_passes
+    }
+
+    /// Removes the recorded passes
+    pub fn clear_recorded_passes(&mut self) {
+        self.passes.clear();
+        self.compute_passes.clear();
+    }
+
+    fn record(&mut self, target: RecordedTarget, renderer: &Renderer) {
+        renderer.passes.iter().for_each(|pass| match pass {
+            Pass::Render(rpass) => self.passes.push(RecordedPass::new(target, rpass)),
+            Pass::Compute(cpass) => self.compute_passes.push(RecordedComputePass::new(cpass)),
+        });
+    }
+}
+
+impl
+    GKDevice<
+        DrawFrame,
+        RenderPipeline,
+        Buffer,
+        Texture,
+        Sampler,
+        BindGroup,
+        BindGroupLayoutRef,
+        RenderTexture,
+        ComputePipeline,
+    > for Device
+{
+    fn new(attrs: GfxAttributes) -> Result<Self, String> {
+        Ok(Self {
+            next_resource_id: 0,
+            attrs,
+            surfaces: HashMap::default(),
+            passes: vec![],
+            compute_passes: vec![],
+        })
+    }
+
+    fn create_frame(&mut self, window_id: WindowId) -> Result<DrawFrame, String> {
+        if !self.surfaces.contains_key(&window_id) {
+            return Err(format!("No surface for {:?}", window_id));
+        }
 
-impl GKDevice<Pipeline> for Device {
-    fn new() -> Result<Self, String>
-    where
-        Self: Sized,
-    {
-        Ok(Self)
+        Ok(DrawFrame { window_id })
     }
-    fn init_surface<W: GKWindow>(&mut self, win: &W) -> Result<(), String> {
+
+    fn present(&mut self, _frame: DrawFrame) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn init_surface<W: GKWindow>(&mut self, window: &W) -> Result<(), String> {
+        if self.attrs.headless {
+            return Err("Cannot initialize a surface on a headless device".to_string());
+        }
+
+        self.surfaces
+            .entry(window.id())
+            .or_insert_with(|| window.physical_size());
         Ok(())
     }
+
     fn create_render_pipeline(
         &mut self,
-        _desc: RenderPipelineDescriptor,
-    ) -> Result<Pipeline, String> {
-        Ok(Pipeline)
+        desc: RenderPipelineDescriptor,
+    ) -> Result<RenderPipeline, String> {
+        if desc.stencil.is_some() && !self.attrs.depth_format.has_stencil() {
+            return Err(format!(
+                "Stencil needs a depth format with stencil, got '{:?}'",
+                self.attrs.depth_format
+            ));
+        }
+
+        let bind_group_layout = desc
+            .bind_group_layout
+            .iter()
+            .map(|layout| BindGroupLayoutRef {
+                id: resource_id(&mut self.next_resource_id),
+                dynamic_uniforms: layout.dynamic_uniforms().into(),
+            })
+            .collect();
+
+        Ok(RenderPipeline {
+            id: resource_id(&mut self.next_resource_id),
+            index_format: desc.index_format,
+            bind_group_layout,
+        })
+    }
+
+    fn create_compute_pipeline(
+        &mut self,
+        desc: ComputePipelineDescriptor,
+    ) -> Result<ComputePipeline, String> {
+        let bind_group_layout = desc
+            .bind_group_layout
+            .iter()
+            .map(|layout| BindGroupLayoutRef {
+                id: resource_id(&mut self.next_resource_id),
+                dynamic_uniforms: layout.dynamic_uniforms().into(),
+            })
+            .collect();
+
+        Ok(ComputePipeline {
+            id: resource_id(&mut self.next_resource_id),
+            bind_group_layout,
+        })
+    }
+
+    fn create_buffer(&mut self, desc: BufferDescriptor) -> Result<Buffer, String> {
+        Ok(Buffer {
+            id: resource_id(&mut self.next_resource_id),
+            usage: desc.usage,
+            write: desc.write,
+            size: desc.content.len(),
+        })
+    }
+
+    fn create_render_texture(
+        &mut self,
+        desc: RenderTextureDescriptor,
+    ) -> Result<RenderTexture, String> {
+        let size = TextureData {
+            bytes: &[],
+            width: desc.width,
+            height: desc.height,
+            mips: &[],
+        };
+
+        let texture = self.create_texture(
+            TextureDescriptor {
+                label: desc.label,
+                format: TextureFormat::Bgra8UnormSrgb,
+                write: true,
+                storage: false,
+                mipmaps: false,
+            },
+            Some(size),
+        )?;
+
+        let depth_texture = if desc.depth {
+            Some(self.create_texture(
+                TextureDescriptor {
+                    label: desc.label,
+                    format: self.attrs.depth_format,
+                    write: true,
+                    storage: false,
+                    mipmaps: false,
+                },
+                Some(size),
+            )?)
+        } else {
+            None
+        };
+
+        Ok(RenderTexture {
+            id: resource_id(&mut self.next_resource_id),
+            texture,
+            depth_texture,
+            samples: desc.samples.max(1),
+        })
+    }
+
+    fn is_format_supported(&self, _format: TextureFormat) -> bool {
+        true
     }
-    fn resize(&mut self, id: WindowId, width: u32, height: u32) {
-        // no-op
+
+    fn create_texture(
+        &mut self,
+        desc: TextureDescriptor,
+        data: Option<TextureData>,
+    ) -> Result<Texture, String> {
+        Ok(Texture {
+            id: resource_id(&mut self.next_resource_id),
+            size: data.map_or((0, 0), |d| (d.width, d.height)),
+            format: desc.format,
+            write: desc.write,
+            mip_levels: data.map_or(1, |d| d.mip_levels(&desc)),
+            mipmaps: desc.mipmaps,
+        })
     }
-    fn render(&mut self, window: WindowId, renderer: &Renderer) -> Result<(), String> {
+
+    fn write_buffer(&mut self, buffer: &Buffer, offset: u64, data: &[u8]) -> Result<(), String> {
+        debug_assert!(buffer.write, "Cannot write data to a static buffer");
+        debug_assert!(
+            offset as usize + data.len() <= buffer.len(),
+            "Invalid buffer size '{}' expected '{}'",
+            buffer.len(),
+            offset as usize + data.len()
+        );
         Ok(())
     }
+
+    fn write_texture(
+        &mut self,
+        texture: &Texture,
+        rect: TextureRect,
+        data: &[u8],
+    ) -> Result<(), String> {
+        debug_assert!(texture.write, "Cannot write data to a static texture");
+        debug_assert!(
+            rect.x + rect.width <= texture.size.0 && rect.y + rect.height <= texture.size.1,
+            "Invalid rect '{:?}' for texture with size '{:?}'",
+            rect,
+            texture.size
+        );
+        debug_assert_eq!(
+            Some(data.len()),
+            texture.format.data_size(rect.width, rect.height)
+        );
+        Ok(())
+    }
+
+    fn read_pixels(&mut self, texture: &Texture, rect: TextureRect) -> Result<Vec<u8>, String> {
+        if texture.format.is_depth() {
+            return Err("Cannot read pixels from a depth texture".to_string());
+        }
+
+        if texture.format.is_compressed() || texture.format.block_bytes() != 4 {
+            return Err(format!(
+                "Cannot read pixels from a texture with format '{:?}'",
+                texture.format
+            ));
+        }
+
+        read_empty_pixels(texture.size, rect)
+    }
+
+    fn read_frame_pixels(
+        &mut self,
+        frame: &DrawFrame,
+        rect: TextureRect,
+    ) -> Result<Vec<u8>, String> {
+        read_empty_pixels(self.size(frame.window_id), rect)
+    }
+
+    fn create_sampler(&mut self, _desc: SamplerDescriptor) -> Result<Sampler, String> {
+        Ok(Sampler {
+            id: resource_id(&mut self.next_resource_id),
+        })
+    }
+
+    fn create_bind_group(&mut self, desc: BindGroupDescriptor) -> Result<BindGroup, String> {
+        if desc.layout.is_none() {
+            return Err("Cannot create binding group with a missing layout.".to_string());
+        }
+
+        Ok(BindGroup {
+            id: resource_id(&mut self.next_resource_id),
+            dynamic_bindings: desc.dynamic_bindings()?.into(),
+        })
+    }
+
+    fn resize(&mut self, id: WindowId, width: u32, height: u32) -> Result<(), String> {
+        if let Some(size) = self.surfaces.get_mut(&id) {
+            *size = (width, height);
+        }
+
+        Ok(())
+    }
+
+    fn size(&self, id: WindowId) -> (u32, u32) {
+        self.surfaces.get(&id).copied().unwrap_or((0, 0))
+    }
+
+    fn render_to_frame(&mut self, frame: &DrawFrame, renderer: &Renderer) -> Result<(), String> {
+        self.record(RecordedTarget::Frame(frame.window_id), renderer);
+        Ok(())
+    }
+
+    fn render_to_texture(
+        &mut self,
+        frame: &RenderTexture,
+        renderer: &Renderer,
+    ) -> Result<(), String> {
+        debug_assert!(
+            frame.texture.write,
+            "Cannot write data to a static render texture"
+        );
+        self.record(RecordedTarget::Texture(frame.id()), renderer);
+        Ok(())
+    }
+
+    fn compute(&mut self, renderer: &Renderer) -> Result<(), String> {
+        renderer.passes.iter().for_each(|pass| {
+            if let Pass::Compute(cpass) = pass {
+                self.compute_passes.push(RecordedComputePass::new(cpass));
+            }
+        });
+        Ok(())
+    }
+}
+
+impl Gfx {
+    /// Passes rendered since the last clean
+    pub fn recorded_passes(&self) -> &[RecordedPass] {
+        self.raw.recorded_passes()
+    }
+
+    /// Compute passes executed since the last clean
+    pub fn recorded_compute_passes(&self) -> &[RecordedComputePass] {
+        self.raw.recorded_compute_passes()
+    }
+
+    /// Removes the recorded passes
+    pub fn clear_recorded_passes(&mut self) {
+        self.raw.clear_recorded_passes();
+    }
+}
+
+fn resource_id<T: From<u64>>(count: &mut u64) -> T {
+    let id = *count;
+    *count += 1;
+    T::from(id)
+}
+
+fn read_empty_pixels(size: (u32, u32), rect: TextureRect) -> Result<Vec<u8>, String> {
+    let TextureRect {
+        x,
+        y,
+        width,
+        height,
+    } = rect;
+    let out_of_bounds = x.checked_add(width).is_none_or(|right| right > size.0)
+        || y.checked_add(height).is_none_or(|bottom| bottom > size.1);
+    if out_of_bounds {
+        return Err(format!(
+            "Invalid rect '{:?}' to read pixels from texture with size '{:?}'",
+            rect, size
+        ));
+    }
+
+    Ok(vec![0; width as usize * height as usize * 4])
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_utils::headless_gfx;
+    use crate::*;
+
+    #[test]
+    fn test_record_passes() {
+        let mut gfx = headless_gfx();
+
+        let pip = gfx.create_render_pipeline("").build().unwrap();
+        let vbo = gfx.create_vertex_buffer(&[0.0f32; 6]).build().unwrap();
+        let rt = gfx
+            .create_render_texture()
+            .with_size(10, 10)
+            .build()
+            .unwrap();
+
+        let mut renderer = Renderer::new();
+        renderer
+            .begin_pass()
+            .clear_color(Color::RED)
+            .pipeline(&pip)
+            .buffers(&[&vbo])
+            .draw(0..3);
+        gfx.render(&rt, &renderer).unwrap();
+
+        let passes = gfx.recorded_passes();
+        assert_eq!(passes.len(), 1);
+        assert_eq!(passes[0].target, RecordedTarget::Texture(rt.id()));
+        assert_eq!(passes[0].pipeline, Some(pip.id()));
+        assert_eq!(
+            passes[0].buffers,
+            vec![RecordedBuffer {
+                id: vbo.id(),
+                offset: 0,
+                size: None,
+            }]
+        );
+        assert_eq!(passes[0].clear_options.color, Some(Color::RED));
+        assert_eq!(
+            passes[0].draws,
+            vec![RecordedDraw {
+                range: 0..3,
+                base_vertex: 0,
+                instances: None,
+                viewport: None,
+                scissor: None,
+                dynamic_offsets: vec![],
+            }]
+        );
+
+        gfx.clear_recorded_passes();
+        assert!(gfx.recorded_passes().is_empty());
+    }
+
+    #[test]
+    fn test_record_compute_passes() {
+        let mut gfx = headless_gfx();
+
+        let shader = "@compute @workgroup_size(64) fn cs_main() {}";
+        let pip = gfx.create_compute_pipeline(shader).build().unwrap();
+        assert!(gfx
+            .create_compute_pipeline(shader)
+            .with_entry("main")
+            .build()
+            .is_err());
+
+        let mut renderer = Renderer::new();
+        renderer
+            .begin_compute_pass()
+            .pipeline(&pip)
+            .dispatch(4, 1, 1)
+            .dispatch(2, 2, 1);
+        gfx.compute(&renderer).unwrap();
+
+        let passes = gfx.recorded_compute_passes();
+        assert_eq!(passes.len(), 1);
+        assert_eq!(passes[0].pipeline, Some(pip.id()));
+        assert_eq!(passes[0].dispatches, vec![(4, 1, 1), (2, 2, 1)]);
+
+        renderer.begin_pass();
+        assert!(gfx.compute(&renderer).is_err());
+    }
+
+    #[test]
+    fn test_read_pixels_bounds() {
+        let rect = |x, y, width, height| TextureRect {
+            x,
+            y,
+            width,
+            height,
+        };
+        let read = super::read_empty_pixels;
+        assert_eq!(read((4, 2), rect(0, 0, 4, 2)).unwrap().len(), 32);
+        assert_eq!(read((4, 2), rect(4, 2, 0, 0)).unwrap().len(), 0);
+        assert!(read((4, 2), rect(1, 0, 4, 2)).is_err());
+        assert!(read((4, 2), rect(u32::MAX, 0, 2, 1)).is_err());
+        assert!(read((4, 2), rect(0, 1, 1, u32::MAX)).is_err());
+    }
 }
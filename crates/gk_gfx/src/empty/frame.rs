@@ -0,0 +1,24 @@
+use crate::frame::GKDrawFrame;
+use crate::render_target::RenderTarget;
+use crate::render_texture::GKRenderTexture;
+use gk_sys::window::WindowId;
+
+#[derive(Debug)]
+pub struct DrawFrame {
+    pub(crate) window_id: WindowId,
+}
+
+impl GKDrawFrame for DrawFrame {
+    fn window_id(&self) -> WindowId {
+        self.window_id
+    }
+}
+
+impl<'a, RT> Into<RenderTarget<'a, DrawFrame, RT>> for &'a DrawFrame
+where
+    RT: GKRenderTexture,
+{
+    fn into(self) -> RenderTarget<'a, DrawFrame, RT> {
+        RenderTarget::Frame(self)
+    }
+}
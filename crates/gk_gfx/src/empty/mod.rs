@@ -1,7 +1,19 @@
+mod bind_group;
 mod buffer;
+mod commands;
 mod device;
+mod frame;
 mod pipeline;
+mod render_texture;
+mod sampler;
+mod texture;
 
+pub use bind_group::*;
 pub use buffer::*;
+pub use commands::*;
 pub use device::*;
+pub use frame::*;
 pub use pipeline::*;
+pub use render_texture::*;
+pub use sampler::*;
+pub use texture::*;
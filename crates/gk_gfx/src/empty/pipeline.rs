@@ -1,4 +1,44 @@
-use crate::GKRenderPipeline;
+use crate::consts::MAX_BIND_GROUPS_PER_PIPELINE;
+use crate::{BindGroupLayoutRef, GKComputePipeline, GKRenderPipeline, IndexFormat, PipelineId};
+use arrayvec::ArrayVec;
 
-pub struct RenderPipeline;
-impl GKRenderPipeline for RenderPipeline {}
+#[derive(Debug, Clone)]
+pub struct RenderPipeline {
+    pub(crate) id: PipelineId,
+    pub(crate) index_format: IndexFormat,
+    pub(crate) bind_group_layout: ArrayVec<BindGroupLayoutRef, MAX_BIND_GROUPS_PER_PIPELINE>,
+}
+
+impl GKRenderPipeline for RenderPipeline {
+    fn id(&self) -> PipelineId {
+        self.id
+    }
+
+    fn index_format(&self) -> IndexFormat {
+        self.index_format
+    }
+
+    fn bind_group_layout_id(&self, index: u32) -> Result<&BindGroupLayoutRef, String> {
+        self.bind_group_layout
+            .get(index as usize)
+            .ok_or_else(|| format!("Invalid Bind Group '{}' in pipeline", index))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ComputePipeline {
+    pub(crate) id: PipelineId,
+    pub(crate) bind_group_layout: ArrayVec<BindGroupLayoutRef, MAX_BIND_GROUPS_PER_PIPELINE>,
+}
+
+impl GKComputePipeline for ComputePipeline {
+    fn id(&self) -> PipelineId {
+        self.id
+    }
+
+    fn bind_group_layout_id(&self, index: u32) -> Result<&BindGroupLayoutRef, String> {
+        self.bind_group_layout
+            .get(index as usize)
+            .ok_or_else(|| format!("Invalid Bind Group '{}' in pipeline", index))
+    }
+}
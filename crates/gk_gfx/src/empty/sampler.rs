@@ -0,0 +1,12 @@
+use crate::texture::{GKSampler, SamplerId};
+
+#[derive(Debug, Clone)]
+pub struct Sampler {
+    pub(crate) id: SamplerId,
+}
+
+impl GKSampler for Sampler {
+    fn id(&self) -> SamplerId {
+        self.id
+    }
+}
@@ -0,0 +1,42 @@
+use crate::texture::{GKTexture, TextureFormat, TextureId};
+
+#[derive(Debug, Clone)]
+pub struct Texture {
+    pub(crate) id: TextureId,
+    pub(crate) size: (u32, u32),
+    pub(crate) format: TextureFormat,
+    pub(crate) write: bool,
+    pub(crate) mip_levels: u32,
+    /// The mip levels are generated from the first one
+    pub(crate) mipmaps: bool,
+}
+
+impl GKTexture for Texture {
+    fn id(&self) -> TextureId {
+        self.id
+    }
+
+    fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    fn width(&self) -> u32 {
+        self.size.0
+    }
+
+    fn height(&self) -> u32 {
+        self.size.1
+    }
+
+    fn mip_levels(&self) -> u32 {
+        self.mip_levels
+    }
+
+    fn format(&self) -> TextureFormat {
+        self.format
+    }
+
+    fn is_writable(&self) -> bool {
+        self.write
+    }
+}
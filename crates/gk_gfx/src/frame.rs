@@ -1 +1,5 @@
-pub trait GKDrawFrame {}
+use gk_sys::window::WindowId;
+
+pub trait GKDrawFrame {
+    fn window_id(&self) -> WindowId;
+}
@@ -1,20 +1,29 @@
+use crate::consts::{DEFAULT_COMPUTE_ENTRY, DEFAULT_FRAGMENT_ENTRY, DEFAULT_VERTEX_ENTRY};
+use crate::recorder::FrameRecorder;
+use crate::reflect::ReflectedLayout;
 use crate::render_target::RenderTarget;
-use crate::render_texture::RenderTextureDescriptor;
+use crate::render_texture::{validate_samples, RenderTextureDescriptor};
 use crate::renderer::Renderer;
+use crate::shader::{ShaderPreprocessor, ShaderRegistry};
+use crate::texture_file::TextureFile;
 use crate::{
     BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutId,
     BindGroupLayoutRef, BlendMode, Buffer, BufferDescriptor, BufferUsage, ColorMask, CompareMode,
-    CullMode, DepthStencil, Device, DrawFrame, GKBuffer, GfxAttributes, GfxConfig, IndexFormat,
-    Primitive, RenderPipeline, RenderTexture, Sampler, SamplerDescriptor, Stencil, Texture,
-    TextureData, TextureDescriptor, TextureFilter, TextureFormat, TextureWrap, VertexLayout,
+    CullMode, DepthStencil, Device, DrawFrame, GKBuffer, GKDrawFrame, GKTexture, GfxAttributes,
+    GfxConfig, IndexFormat, Primitive, RenderPipeline, RenderTexture, Sampler, SamplerDescriptor,
+    Stencil, Texture, TextureData, TextureDescriptor, TextureFilter, TextureFormat, TextureRect,
+    TextureWrap, VertexLayout,
 };
-use crate::{GKDevice, RenderPipelineDescriptor};
+use crate::{ComputePipeline, ComputePipelineDescriptor, GKDevice, RenderPipelineDescriptor};
 use gk_sys::window::{GKWindow, WindowId};
 use gk_sys::Plugin;
 use image::EncodableLayout;
+use std::path::Path;
 
 pub struct Gfx {
     pub(crate) raw: Device,
+    pub(crate) recorder: Option<FrameRecorder>,
+    pub(crate) shaders: ShaderRegistry,
 }
 
 impl Plugin for Gfx {}
@@ -24,8 +33,13 @@ where
     Self: 'b,
 {
     pub fn new(attrs: GfxAttributes) -> Result<Self, String> {
+        validate_samples(attrs.msaa_samples)?;
         let raw = Device::new(attrs)?;
-        Ok(Self { raw })
+        Ok(Self {
+            raw,
+            recorder: None,
+            shaders: ShaderRegistry::default(),
+        })
     }
 
     pub fn config() -> GfxConfig {
@@ -40,10 +54,26 @@ where
         self.raw.init_surface(win)
     }
 
+    /// Register a WGSL source to be used by other shaders with `#include "name"`
+    pub fn add_shader_include(&mut self, name: &str, source: &str) {
+        self.shaders.add(name, source);
+    }
+
+    pub fn shader_registry(&self) -> &ShaderRegistry {
+        &self.shaders
+    }
+
     pub fn create_render_pipeline<'a>(&'a mut self, shader: &'a str) -> RenderPipelineBuilder {
         RenderPipelineBuilder::new(self, shader)
     }
 
+    pub fn create_compute_pipeline<'a>(
+        &'a mut self,
+        shader: &'a str,
+    ) -> ComputePipelineBuilder<'a> {
+        ComputePipelineBuilder::new(self, shader)
+    }
+
     pub fn create_vertex_buffer<'a, D: bytemuck::Pod>(
         &'a mut self,
         data: &'a [D],
@@ -62,6 +92,18 @@ where
         BufferBuilder::new(self, BufferUsage::Uniform, data)
     }
 
+    pub fn create_storage_buffer<'a, D: bytemuck::Pod>(
+        &'a mut self,
+        data: &'a [D],
+    ) -> BufferBuilder {
+        BufferBuilder::new(self, BufferUsage::Storage, data)
+    }
+
+    /// Compressed formats depend on the adapter
+    pub fn is_format_supported(&self, format: TextureFormat) -> bool {
+        self.raw.is_format_supported(format)
+    }
+
     pub fn create_texture(&mut self) -> TextureBuilder {
         TextureBuilder::new(self)
     }
@@ -74,6 +116,31 @@ where
         BufferWriteBuilder::new(self, buffer)
     }
 
+    /// Writes on the first mip level, the rest of levels are generated again if the texture uses mipmaps
+    pub fn write_texture<'a>(&'a mut self, texture: &'a Texture) -> TextureWriteBuilder<'a> {
+        TextureWriteBuilder::new(self, texture)
+    }
+
+    pub fn read_pixels<'a>(&'a mut self, texture: &'a Texture) -> TextureReadBuilder<'a> {
+        TextureReadBuilder::new(self, ReadSource::Texture(texture))
+    }
+
+    /// Read the pixels of a frame, it must be called before present it
+    pub fn read_frame_pixels<'a>(&'a mut self, frame: &'a DrawFrame) -> TextureReadBuilder<'a> {
+        TextureReadBuilder::new(self, ReadSource::Frame(frame))
+    }
+
+    /// Saves the next `frames` presented frames as PNG images inside `dir`
+    /// Only frames passed to `present` are captured, RenderTextures are never recorded
+    pub fn record_frames(&mut self, dir: &str, frames: usize) {
+        self.recorder = Some(FrameRecorder::new(dir, frames));
+    }
+
+    /// Returns the current frame recorder if any
+    pub fn recorder(&self) -> Option<&FrameRecorder> {
+        self.recorder.as_ref()
+    }
+
     pub fn create_sampler(&mut self) -> SamplerBuilder {
         SamplerBuilder::new(self)
     }
@@ -94,20 +161,72 @@ where
     where
         T: Into<RenderTarget<'a, DrawFrame, RenderTexture>>,
     {
+        renderer.validate()?;
         match target.into() {
             RenderTarget::Frame(frame) => self.raw.render_to_frame(frame, renderer),
             RenderTarget::Texture(texture) => self.raw.render_to_texture(texture, renderer),
         }
     }
 
+    /// Execute the compute passes of the renderer, render passes are not allowed
+    pub fn compute(&mut self, renderer: &Renderer) -> Result<(), String> {
+        if renderer.has_render_passes() {
+            return Err("Render passes need a target, use Gfx::render instead".to_string());
+        }
+
+        renderer.validate()?;
+        self.raw.compute(renderer)
+    }
+
     pub fn present(&mut self, frame: DrawFrame) -> Result<(), String> {
+        // a failed capture must not lose the frame, the recording stops instead
+        if let Err(e) = self.record_frame(&frame) {
+            log::error!("Frame recording stopped: {}", e);
+            self.recorder = None;
+        }
+
         self.raw.present(frame)
     }
+
+    fn record_frame(&mut self, frame: &DrawFrame) -> Result<(), String> {
+        // only the first frame presented is captured (in case of multiple windows)
+        let path = match &self.recorder {
+            Some(recorder) if !recorder.captured && !recorder.is_done() => recorder.frame_path(),
+            _ => return Ok(()),
+        };
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+
+        self.read_frame_pixels(frame).save_png(&path)?;
+        if let Some(recorder) = &mut self.recorder {
+            recorder.captured = true;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn frame_end(&mut self) {
+        if let Some(recorder) = &mut self.recorder {
+            recorder.frame_end();
+            if recorder.is_done() {
+                log::info!(
+                    "Recorded {} frames in '{}'",
+                    recorder.recorded,
+                    recorder.dir.display()
+                );
+                self.recorder = None;
+            }
+        }
+    }
 }
 
 pub struct RenderPipelineBuilder<'a> {
     gfx: &'a mut Gfx,
     desc: RenderPipelineDescriptor<'a>,
+    defines: Vec<(&'a str, &'a str)>,
+    reflect: bool,
 }
 
 impl<'a> RenderPipelineBuilder<'a> {
@@ -116,7 +235,12 @@ impl<'a> RenderPipelineBuilder<'a> {
             shader,
             ..Default::default()
         };
-        Self { desc, gfx }
+        Self {
+            desc,
+            gfx,
+            defines: vec![],
+            reflect: false,
+        }
     }
 
     pub fn with_label(mut self, label: &'a str) -> Self {
@@ -124,6 +248,21 @@ impl<'a> RenderPipelineBuilder<'a> {
         self
     }
 
+    /// Define used by the shader preprocessor, an empty value can be used for `#ifdef` flags
+    pub fn with_define(mut self, name: &'a str, value: &'a str) -> Self {
+        self.defines.push((name, value));
+        self
+    }
+
+    /// Derive the vertex and bind group layouts from the shader when they are not set,
+    /// or check that the ones set match the shader
+    /// The derived vertex layout packs every `@location` input in location order into
+    /// one interleaved buffer, shaders that need more buffers must set their vertex layouts
+    pub fn with_reflected_layout(mut self) -> Self {
+        self.reflect = true;
+        self
+    }
+
     pub fn with_vertex_layout(mut self, layout: VertexLayout) -> Self {
         self.desc.vertex_layout.push(layout);
         self
@@ -183,8 +322,107 @@ impl<'a> RenderPipelineBuilder<'a> {
     }
 
     pub fn build(self) -> Result<RenderPipeline, String> {
-        let Self { desc, gfx } = self;
-        gfx.raw.create_render_pipeline(desc)
+        let Self {
+            mut desc,
+            gfx,
+            defines,
+            reflect,
+        } = self;
+        let shader = defines
+            .iter()
+            .fold(
+                ShaderPreprocessor::new(&gfx.shaders),
+                |pp, (name, value)| pp.with_define(name, value),
+            )
+            .process(desc.label.unwrap_or("shader"), desc.shader)?;
+
+        if reflect {
+            let (module, info) = shader.parse()?;
+            ReflectedLayout::new(
+                &module,
+                &info,
+                desc.vs_entry.unwrap_or(DEFAULT_VERTEX_ENTRY),
+                desc.fs_entry.unwrap_or(DEFAULT_FRAGMENT_ENTRY),
+            )?
+            .apply(&mut desc)?;
+        } else {
+            shader.validate()?;
+        }
+
+        gfx.raw.create_render_pipeline(RenderPipelineDescriptor {
+            shader: shader.code(),
+            ..desc
+        })
+    }
+}
+
+pub struct ComputePipelineBuilder<'a> {
+    gfx: &'a mut Gfx,
+    desc: ComputePipelineDescriptor<'a>,
+    defines: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> ComputePipelineBuilder<'a> {
+    fn new(gfx: &'a mut Gfx, shader: &'a str) -> Self {
+        let desc = ComputePipelineDescriptor {
+            shader,
+            ..Default::default()
+        };
+        Self {
+            gfx,
+            desc,
+            defines: vec![],
+        }
+    }
+
+    pub fn with_label(mut self, label: &'a str) -> Self {
+        self.desc.label = Some(label);
+        self
+    }
+
+    /// Define used by the shader preprocessor, an empty value can be used for `#ifdef` flags
+    pub fn with_define(mut self, name: &'a str, value: &'a str) -> Self {
+        self.defines.push((name, value));
+        self
+    }
+
+    pub fn with_bind_group_layout(mut self, layout: BindGroupLayout) -> Self {
+        self.desc.bind_group_layout.push(layout);
+        self
+    }
+
+    pub fn with_entry(mut self, entry: &'a str) -> Self {
+        self.desc.entry = Some(entry);
+        self
+    }
+
+    pub fn build(self) -> Result<ComputePipeline, String> {
+        let Self { gfx, desc, defines } = self;
+        let shader = defines
+            .iter()
+            .fold(
+                ShaderPreprocessor::new(&gfx.shaders),
+                |pp, (name, value)| pp.with_define(name, value),
+            )
+            .process(desc.label.unwrap_or("shader"), desc.shader)?;
+
+        let (module, _) = shader.parse()?;
+        let entry = desc.entry.unwrap_or(DEFAULT_COMPUTE_ENTRY);
+        let found = module
+            .entry_points
+            .iter()
+            .any(|ep| ep.name == entry && ep.stage == naga::ShaderStage::Compute);
+        if !found {
+            return Err(format!(
+                "Shader entry point '{}' (Compute) not found",
+                entry
+            ));
+        }
+
+        gfx.raw.create_compute_pipeline(ComputePipelineDescriptor {
+            shader: shader.code(),
+            ..desc
+        })
     }
 }
 
@@ -233,20 +471,37 @@ pub struct TextureBuilder<'a> {
     gfx: &'a mut Gfx,
     desc: TextureDescriptor<'a>,
     data: TextureRawData<'a>,
+    mips: &'a [&'a [u8]],
 }
 
 impl<'a> TextureBuilder<'a> {
     pub fn new(gfx: &'a mut Gfx) -> Self {
         let desc = TextureDescriptor::default();
         let data = TextureRawData::Empty;
-        Self { gfx, desc, data }
+        Self {
+            gfx,
+            desc,
+            data,
+            mips: &[],
+        }
     }
 
+    /// Encoded image, KTX2 and DDS files are uploaded using the format and mip levels they contain
     pub fn from_image(mut self, image: &'a [u8]) -> Self {
         self.data = TextureRawData::Image(image);
         self
     }
 
+    /// Raw pixels using the texture's format
+    pub fn from_bytes(mut self, bytes: &'a [u8], width: u32, height: u32) -> Self {
+        self.data = TextureRawData::Raw {
+            bytes,
+            width,
+            height,
+        };
+        self
+    }
+
     pub fn with_label(mut self, label: &'a str) -> Self {
         self.desc.label = Some(label);
         self
@@ -262,33 +517,92 @@ impl<'a> TextureBuilder<'a> {
         self
     }
 
+    /// Allow to use the texture as storage texture, the format must support it
+    pub fn with_storage_flag(mut self, storage: bool) -> Self {
+        self.desc.storage = storage;
+        self
+    }
+
+    /// Generate the full mip chain on the GPU from the texture's data
+    pub fn with_mipmaps(mut self, enabled: bool) -> Self {
+        self.desc.mipmaps = enabled;
+        self
+    }
+
+    /// Upload the mip levels after the first one instead of generating them,
+    /// each level must be half the size of the previous one
+    pub fn with_mip_data(mut self, levels: &'a [&'a [u8]]) -> Self {
+        self.mips = levels;
+        self
+    }
+
     pub fn build(self) -> Result<Texture, String> {
-        let Self { gfx, desc, data } = self;
+        let Self {
+            gfx,
+            desc,
+            data,
+            mips,
+        } = self;
+
+        let create = |gfx: &mut Gfx, desc: TextureDescriptor, data: TextureData| {
+            data.validate(&desc)?;
+            gfx.raw.create_texture(desc, Some(data))
+        };
+
         match data {
+            TextureRawData::Empty if !mips.is_empty() => {
+                Err("Cannot upload mip levels to a texture without data".to_string())
+            }
             TextureRawData::Empty => gfx.raw.create_texture(desc, None),
             TextureRawData::Image(bytes) => {
+                if let Some(file) = TextureFile::parse(bytes) {
+                    let file = file?;
+                    if !mips.is_empty() {
+                        return Err("The texture file already contains the mip levels".to_string());
+                    }
+
+                    let desc = TextureDescriptor {
+                        format: file.format,
+                        ..desc
+                    };
+                    return create(
+                        gfx,
+                        desc,
+                        TextureData {
+                            bytes: file.levels[0],
+                            width: file.width,
+                            height: file.height,
+                            mips: &file.levels[1..],
+                        },
+                    );
+                }
+
                 let img = image::load_from_memory(bytes).map_err(|e| e.to_string())?;
                 let rgba = img.to_rgba8();
-                gfx.raw.create_texture(
+                create(
+                    gfx,
                     desc,
-                    Some(TextureData {
+                    TextureData {
                         bytes: rgba.as_bytes(),
                         width: rgba.width(),
                         height: rgba.height(),
-                    }),
+                        mips,
+                    },
                 )
             }
             TextureRawData::Raw {
                 bytes,
                 width,
                 height,
-            } => gfx.raw.create_texture(
+            } => create(
+                gfx,
                 desc,
-                Some(TextureData {
+                TextureData {
                     bytes,
                     width,
                     height,
-                }),
+                    mips,
+                },
             ),
         }
     }
@@ -372,14 +686,54 @@ impl<'a> BindGroupBuilder<'a> {
     }
 
     pub fn with_uniform(mut self, location: u32, buffer: &'a Buffer) -> Self {
+        self.desc.entry.push(BindGroupEntry::Uniform {
+            location,
+            buffer,
+            size: None,
+        });
+        self
+    }
+
+    /// Binds only `size` bytes, the offset is set with `RenderPass::dynamic_offsets`
+    pub fn with_dynamic_uniform(mut self, location: u32, buffer: &'a Buffer, size: u64) -> Self {
+        self.desc.entry.push(BindGroupEntry::Uniform {
+            location,
+            buffer,
+            size: Some(size),
+        });
+        self
+    }
+
+    pub fn with_storage_buffer(mut self, location: u32, buffer: &'a Buffer) -> Self {
+        self.desc
+            .entry
+            .push(BindGroupEntry::StorageBuffer { location, buffer });
+        self
+    }
+
+    pub fn with_storage_texture(mut self, location: u32, texture: &'a Texture) -> Self {
         self.desc
             .entry
-            .push(BindGroupEntry::Uniform { location, buffer });
+            .push(BindGroupEntry::StorageTexture { location, texture });
         self
     }
 
     pub fn build(self) -> Result<BindGroup, String> {
         let Self { gfx, desc } = self;
+        desc.entry.iter().try_for_each(|entry| match entry {
+            BindGroupEntry::Uniform {
+                location,
+                buffer,
+                size: Some(size),
+            } if *size == 0 || *size > buffer.len() as u64 => Err(format!(
+                "Invalid size '{}' for the uniform '{}' with a buffer of '{}' bytes",
+                size,
+                location,
+                buffer.len()
+            )),
+            _ => Ok(()),
+        })?;
+
         gfx.raw.create_bind_group(desc)
     }
 }
@@ -424,10 +778,198 @@ impl<'a> BufferWriteBuilder<'a> {
         }
 
         let data = data.unwrap_or(&[]);
+        let fits = offset
+            .checked_add(data.len() as u64)
+            .is_some_and(|end| end <= buffer.len() as u64);
+        if !fits {
+            return Err(format!(
+                "Cannot write '{}' bytes at offset '{}' in a buffer of '{}' bytes",
+                data.len(),
+                offset,
+                buffer.len()
+            ));
+        }
+
         gfx.raw.write_buffer(buffer, offset, data)
     }
 }
 
+pub struct TextureWriteBuilder<'a> {
+    gfx: &'a mut Gfx,
+    texture: &'a Texture,
+    rect: Option<TextureRect>,
+    data: Option<&'a [u8]>,
+}
+
+impl<'a> TextureWriteBuilder<'a> {
+    pub fn new(gfx: &'a mut Gfx, texture: &'a Texture) -> Self {
+        Self {
+            gfx,
+            texture,
+            rect: None,
+            data: None,
+        }
+    }
+
+    /// Region to update, by default it's the whole texture
+    pub fn with_region(mut self, x: u32, y: u32, width: u32, height: u32) -> Self {
+        self.rect = Some(TextureRect {
+            x,
+            y,
+            width,
+            height,
+        });
+        self
+    }
+
+    /// Pixels of the region using the texture's format
+    pub fn with_data(mut self, data: &'a [u8]) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    pub fn build(self) -> Result<(), String> {
+        let Self {
+            gfx,
+            texture,
+            rect,
+            data,
+        } = self;
+
+        if !texture.is_writable() {
+            return Err("Texture is not Writable".to_string());
+        }
+
+        let format = texture.format();
+        if format.is_depth() {
+            return Err("Cannot write data to a depth texture".to_string());
+        }
+
+        // generated mip levels are updated from the first one, explicit ones cannot be
+        if texture.mip_levels() > 1 && !texture.mipmaps {
+            return Err("Cannot write data to a texture with explicit mip levels".to_string());
+        }
+
+        let (width, height) = texture.size();
+        let rect = rect.unwrap_or(TextureRect {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        });
+
+        let out_of_bounds = rect.x.checked_add(rect.width).is_none_or(|x| x > width)
+            || rect.y.checked_add(rect.height).is_none_or(|y| y > height);
+        if out_of_bounds {
+            return Err(format!(
+                "Invalid region '{:?}' to write on texture with size '{:?}'",
+                rect,
+                (width, height)
+            ));
+        }
+
+        let (block_width, block_height) = format.block_dimensions();
+        let aligned = rect.x % block_width == 0
+            && rect.y % block_height == 0
+            && rect.width % block_width == 0
+            && rect.height % block_height == 0;
+        if !aligned {
+            return Err(format!(
+                "Region '{:?}' must be aligned to the blocks '{:?}' of the format '{:?}'",
+                rect,
+                (block_width, block_height),
+                format
+            ));
+        }
+
+        let data = data.unwrap_or(&[]);
+        let expected = format.data_size(rect.width, rect.height);
+        if expected != Some(data.len()) {
+            return Err(format!(
+                "Invalid data size '{}' for region '{:?}', expected '{}'",
+                data.len(),
+                rect,
+                expected.unwrap_or(usize::MAX)
+            ));
+        }
+
+        gfx.raw.write_texture(texture, rect, data)
+    }
+}
+
+enum ReadSource<'a> {
+    Texture(&'a Texture),
+    Frame(&'a DrawFrame),
+}
+
+pub struct TextureReadBuilder<'a> {
+    gfx: &'a mut Gfx,
+    source: ReadSource<'a>,
+    rect: Option<TextureRect>,
+}
+
+impl<'a> TextureReadBuilder<'a> {
+    fn new(gfx: &'a mut Gfx, source: ReadSource<'a>) -> Self {
+        Self {
+            gfx,
+            source,
+            rect: None,
+        }
+    }
+
+    /// Region to read, by default it's the whole texture
+    pub fn with_rect(mut self, x: u32, y: u32, width: u32, height: u32) -> Self {
+        self.rect = Some(TextureRect {
+            x,
+            y,
+            width,
+            height,
+        });
+        self
+    }
+
+    /// Returns the pixels as RGBA bytes, this will block until the GPU is done
+    pub fn build(self) -> Result<Vec<u8>, String> {
+        self.read().map(|(bytes, _)| bytes)
+    }
+
+    /// Saves the pixels as a PNG image
+    pub fn save_png<P: AsRef<Path>>(self, path: P) -> Result<(), String> {
+        let (bytes, rect) = self.read()?;
+        image::save_buffer_with_format(
+            path,
+            &bytes,
+            rect.width,
+            rect.height,
+            image::ColorType::Rgba8,
+            image::ImageFormat::Png,
+        )
+        .map_err(|e| e.to_string())
+    }
+
+    fn read(self) -> Result<(Vec<u8>, TextureRect), String> {
+        let Self { gfx, source, rect } = self;
+        let (width, height) = match source {
+            ReadSource::Texture(texture) => texture.size(),
+            ReadSource::Frame(frame) => gfx.size(frame.window_id()),
+        };
+
+        let rect = rect.unwrap_or(TextureRect {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        });
+
+        let bytes = match source {
+            ReadSource::Texture(texture) => gfx.raw.read_pixels(texture, rect),
+            ReadSource::Frame(frame) => gfx.raw.read_frame_pixels(frame, rect),
+        }?;
+
+        Ok((bytes, rect))
+    }
+}
+
 pub struct RenderTextureBuilder<'a> {
     gfx: &'a mut Gfx,
     desc: RenderTextureDescriptor<'a>,
@@ -435,7 +977,10 @@ pub struct RenderTextureBuilder<'a> {
 
 impl<'a> RenderTextureBuilder<'a> {
     pub fn new(gfx: &'a mut Gfx) -> Self {
-        let desc = RenderTextureDescriptor::default();
+        let desc = RenderTextureDescriptor {
+            samples: 1,
+            ..Default::default()
+        };
         Self { gfx, desc }
     }
 
@@ -455,6 +1000,12 @@ impl<'a> RenderTextureBuilder<'a> {
         self
     }
 
+    /// Render using MSAA with the given samples (1 or 4), pipelines follow the texture's samples
+    pub fn with_samples(mut self, samples: u32) -> Self {
+        self.desc.samples = samples;
+        self
+    }
+
     pub fn build(self) -> Result<RenderTexture, String> {
         let Self { gfx, desc } = self;
 
@@ -466,6 +1017,85 @@ impl<'a> RenderTextureBuilder<'a> {
             ));
         }
 
+        validate_samples(desc.samples)?;
         gfx.raw.create_render_texture(desc)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::test_utils::headless_gfx;
+
+    #[test]
+    fn test_write_texture() {
+        let mut gfx = headless_gfx();
+
+        let pixels = [0; 4 * 4 * 4];
+        let texture = gfx
+            .create_texture()
+            .from_bytes(&pixels, 4, 4)
+            .with_write_flag(true)
+            .build()
+            .unwrap();
+
+        let data = [255; 2 * 2 * 4];
+        gfx.write_texture(&texture)
+            .with_region(2, 2, 2, 2)
+            .with_data(&data)
+            .build()
+            .unwrap();
+        assert!(gfx
+            .write_texture(&texture)
+            .with_region(3, 2, 2, 2)
+            .with_data(&data)
+            .build()
+            .is_err());
+        assert!(gfx
+            .write_texture(&texture)
+            .with_region(0, 0, 2, 1)
+            .with_data(&data)
+            .build()
+            .is_err());
+
+        let static_texture = gfx
+            .create_texture()
+            .from_bytes(&pixels, 4, 4)
+            .build()
+            .unwrap();
+        assert!(gfx
+            .write_texture(&static_texture)
+            .with_data(&pixels)
+            .build()
+            .is_err());
+
+        let mipmapped = gfx
+            .create_texture()
+            .from_bytes(&pixels, 4, 4)
+            .with_write_flag(true)
+            .with_mipmaps(true)
+            .build()
+            .unwrap();
+        assert!(gfx
+            .write_texture(&mipmapped)
+            .with_data(&pixels)
+            .build()
+            .is_ok());
+
+        let mip = [0; 2 * 2 * 4];
+        let mips: &[&[u8]] = &[&mip];
+        let explicit_mips = gfx
+            .create_texture()
+            .from_bytes(&pixels, 4, 4)
+            .with_write_flag(true)
+            .with_mip_data(mips)
+            .build()
+            .unwrap();
+        assert_eq!(
+            gfx.write_texture(&explicit_mips)
+                .with_data(&pixels)
+                .build()
+                .unwrap_err(),
+            "Cannot write data to a texture with explicit mip levels"
+        );
+    }
+}
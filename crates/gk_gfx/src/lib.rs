@@ -1,15 +1,23 @@
-#[cfg(not(feature = "wgpu"))]
+// tests always use the empty backend to check the recorded commands
+#[cfg(any(test, not(feature = "wgpu")))]
 mod empty;
 
-#[cfg(not(feature = "wgpu"))]
+#[cfg(any(test, not(feature = "wgpu")))]
 pub use crate::empty::*;
 
-#[cfg(feature = "wgpu")]
+#[cfg(all(feature = "wgpu", not(test)))]
 mod wgpu;
 
-#[cfg(feature = "wgpu")]
+#[cfg(all(feature = "wgpu", not(test)))]
 pub use crate::wgpu::*;
 
+// the conversions to wgpu types are still tested without the wgpu device
+#[cfg(all(feature = "wgpu", test))]
+#[allow(dead_code)]
+#[path = "wgpu/utils.rs"]
+mod wgpu_utils;
+
+mod asset;
 mod attrs;
 mod bind_group;
 mod blend_mode;
@@ -20,8 +28,16 @@ mod consts;
 mod device;
 mod gfx;
 mod pipeline;
+mod recorder;
+mod reflect;
 mod renderer;
+mod shader;
 mod texture;
+mod texture_file;
+mod uniform_ring;
+
+#[cfg(test)]
+mod test_utils;
 
 mod frame;
 
@@ -38,8 +54,12 @@ pub use buffer::*;
 pub use color::Color;
 pub use config::*;
 pub use device::*;
+pub use frame::*;
 pub use gfx::*;
 pub use pipeline::*;
+pub use recorder::*;
 pub use render_texture::*;
 pub use renderer::*;
+pub use shader::*;
 pub use texture::*;
+pub use uniform_ring::*;
@@ -9,9 +9,23 @@ pub struct PipelineId(u64);
 
 pub trait GKRenderPipeline {
     fn id(&self) -> PipelineId;
+    fn index_format(&self) -> IndexFormat;
     fn bind_group_layout_id(&self, index: u32) -> Result<&BindGroupLayoutRef, String>;
 }
 
+pub trait GKComputePipeline {
+    fn id(&self) -> PipelineId;
+    fn bind_group_layout_id(&self, index: u32) -> Result<&BindGroupLayoutRef, String>;
+}
+
+#[derive(Default, Clone)]
+pub struct ComputePipelineDescriptor<'a> {
+    pub label: Option<&'a str>,
+    pub shader: &'a str,
+    pub bind_group_layout: ArrayVec<BindGroupLayout, MAX_BIND_GROUPS_PER_PIPELINE>,
+    pub entry: Option<&'a str>,
+}
+
 // https://github.com/floooh/sokol/blob/master/sokol_gfx.h#L2213
 
 #[derive(Default, Clone)]
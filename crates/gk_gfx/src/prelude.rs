@@ -5,7 +5,9 @@ pub use crate::buffer::*;
 pub use crate::color::Color;
 pub use crate::config::*;
 pub use crate::device::*;
+pub use crate::frame::*;
 pub use crate::gfx::*;
 pub use crate::pipeline::*;
+pub use crate::recorder::*;
 pub use crate::renderer::*;
 pub use crate::texture::*;
@@ -0,0 +1,45 @@
+use std::path::PathBuf;
+
+/// Saves the presented frames as a numbered sequence of PNG images
+#[derive(Debug, Clone)]
+pub struct FrameRecorder {
+    pub(crate) dir: PathBuf,
+    pub(crate) frames: usize,
+    pub(crate) recorded: usize,
+    pub(crate) captured: bool,
+}
+
+impl FrameRecorder {
+    /// Records `frames` frames inside the directory `dir`
+    pub fn new(dir: &str, frames: usize) -> Self {
+        Self {
+            dir: PathBuf::from(dir),
+            frames,
+            recorded: 0,
+            captured: false,
+        }
+    }
+
+    /// Path for the image of the current frame
+    pub(crate) fn frame_path(&self) -> PathBuf {
+        self.dir.join(format!("frame_{:05}.png", self.recorded))
+    }
+
+    /// Number of frames already saved
+    pub fn recorded(&self) -> usize {
+        self.recorded
+    }
+
+    /// Returns true once all the frames were saved
+    pub fn is_done(&self) -> bool {
+        self.recorded >= self.frames
+    }
+
+    /// Moves to the next frame if the current one was captured
+    pub(crate) fn frame_end(&mut self) {
+        if self.captured {
+            self.captured = false;
+            self.recorded += 1;
+        }
+    }
+}
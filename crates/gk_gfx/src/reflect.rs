@@ -0,0 +1,541 @@
+use crate::consts::{MAX_BIND_GROUPS_PER_PIPELINE, MAX_VERTEX_ATTRIBUTES};
+use crate::{
+    BindGroupLayout, BindingType, RenderPipelineDescriptor, TextureFormat, VertexFormat,
+    VertexLayout,
+};
+use hashbrown::HashSet;
+use naga::{
+    AddressSpace, Binding, Expression, GlobalVariable, Handle, ImageClass, ImageDimension, Module,
+    ScalarKind, ShaderStage, StorageAccess, StorageFormat, TypeInner,
+};
+
+/// Layout derived from the vertex and fragment entry points of a shader
+/// All the `@location` inputs of the vertex entry are packed in location order
+/// into one interleaved vertex buffer stepped per vertex
+#[derive(Clone, Default)]
+pub(crate) struct ReflectedLayout {
+    attributes: Vec<(u32, VertexFormat)>,
+    groups: Vec<Vec<BindingType>>,
+}
+
+impl ReflectedLayout {
+    pub fn new(
+        module: &Module,
+        info: &naga::valid::ModuleInfo,
+        vs_entry: &str,
+        fs_entry: &str,
+    ) -> Result<Self, String> {
+        let find_entry = |name: &str, stage: ShaderStage| {
+            module
+                .entry_points
+                .iter()
+                .position(|ep| ep.name == name && ep.stage == stage)
+                .ok_or_else(|| format!("Shader entry point '{}' ({:?}) not found", name, stage))
+        };
+        let vs_index = find_entry(vs_entry, ShaderStage::Vertex)?;
+        let fs_index = find_entry(fs_entry, ShaderStage::Fragment)?;
+
+        let mut attributes = vec![];
+        for arg in &module.entry_points[vs_index].function.arguments {
+            match (&arg.binding, &module.types[arg.ty].inner) {
+                (Some(binding), inner) => {
+                    if let Some(attr) = vertex_attribute(binding, inner)? {
+                        attributes.push(attr);
+                    }
+                }
+                (None, TypeInner::Struct { members, .. }) => {
+                    for member in members {
+                        let inner = &module.types[member.ty].inner;
+                        if let Some(binding) = &member.binding {
+                            if let Some(attr) = vertex_attribute(binding, inner)? {
+                                attributes.push(attr);
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        attributes.sort_by_key(|(location, _)| *location);
+
+        let sampled = sampled_textures(module);
+        let vs_info = info.get_entry_point(vs_index);
+        let fs_info = info.get_entry_point(fs_index);
+        let mut groups: Vec<Vec<BindingType>> = vec![];
+        for (handle, var) in module.global_variables.iter() {
+            let Some(res) = &var.binding else {
+                continue;
+            };
+
+            let name = var.name.as_deref().unwrap_or("?");
+            let group = res.group as usize;
+            if group >= MAX_BIND_GROUPS_PER_PIPELINE {
+                return Err(format!(
+                    "Shader binding '{}' uses group {} but the max is {}",
+                    name,
+                    group,
+                    MAX_BIND_GROUPS_PER_PIPELINE - 1
+                ));
+            }
+
+            let unsupported = || {
+                format!(
+                    "Shader binding '{}' (group {}, binding {}) uses an unsupported type",
+                    name, res.group, res.binding
+                )
+            };
+
+            let mut binding = match (&var.space, &module.types[var.ty].inner) {
+                (AddressSpace::Uniform, _) => BindingType::uniform(res.binding),
+                (AddressSpace::Storage { access }, _) => BindingType::storage_buffer(res.binding)
+                    .with_read_only(!access.contains(StorageAccess::STORE)),
+                (
+                    AddressSpace::Handle,
+                    TypeInner::Image {
+                        dim: ImageDimension::D2,
+                        arrayed: false,
+                        class:
+                            ImageClass::Storage {
+                                format,
+                                access: StorageAccess::STORE,
+                            },
+                    },
+                ) => {
+                    let format = storage_texture_format(*format).ok_or_else(unsupported)?;
+                    BindingType::storage_texture(res.binding, format)
+                }
+                (AddressSpace::Handle, TypeInner::Sampler { comparison: false }) => {
+                    BindingType::sampler(res.binding)
+                }
+                (
+                    AddressSpace::Handle,
+                    TypeInner::Image {
+                        dim: ImageDimension::D2,
+                        arrayed: false,
+                        class:
+                            ImageClass::Sampled {
+                                kind: ScalarKind::Float,
+                                multi: false,
+                            },
+                    },
+                ) => {
+                    // textures only read with `textureLoad` can use non-filterable formats
+                    let filterable = sampled.as_ref().is_none_or(|set| set.contains(&handle));
+                    BindingType::texture(res.binding).with_filterable(filterable)
+                }
+                _ => return Err(unsupported()),
+            };
+
+            let in_vertex = !vs_info[handle].is_empty();
+            let in_fragment = !fs_info[handle].is_empty();
+            let unused = !(in_vertex || in_fragment);
+            binding.visible_vertex = in_vertex || unused;
+            binding.visible_fragment = in_fragment || unused;
+
+            if groups.len() <= group {
+                groups.resize(group + 1, vec![]);
+            }
+            groups[group].push(binding);
+        }
+
+        groups
+            .iter_mut()
+            .for_each(|entries| entries.sort_by_key(|b| b.location));
+
+        Ok(Self { attributes, groups })
+    }
+
+    /// Fill the empty vertex and bind group layouts or check that the given ones match the shader
+    pub fn apply(&self, desc: &mut RenderPipelineDescriptor) -> Result<(), String> {
+        if desc.vertex_layout.is_empty() {
+            if self.attributes.len() > MAX_VERTEX_ATTRIBUTES {
+                return Err(format!(
+                    "The shader uses '{}' vertex attributes but one vertex buffer can have up to '{}', set the vertex layouts with `with_vertex_layout`",
+                    self.attributes.len(),
+                    MAX_VERTEX_ATTRIBUTES
+                ));
+            }
+
+            if !self.attributes.is_empty() {
+                let layout = self
+                    .attributes
+                    .iter()
+                    .fold(VertexLayout::new(), |layout, (location, format)| {
+                        layout.with_attr(*location as _, *format)
+                    });
+                desc.vertex_layout.push(layout);
+            }
+        } else {
+            self.check_vertex_layout(&desc.vertex_layout)?;
+        }
+
+        if desc.bind_group_layout.is_empty() {
+            self.groups.iter().for_each(|entries| {
+                let layout = entries
+                    .iter()
+                    .fold(BindGroupLayout::new(), |layout, binding| {
+                        layout.with_entry(*binding)
+                    });
+                desc.bind_group_layout.push(layout);
+            });
+        } else {
+            self.check_bind_group_layout(&desc.bind_group_layout)?;
+        }
+
+        Ok(())
+    }
+
+    fn check_vertex_layout(&self, layouts: &[VertexLayout]) -> Result<(), String> {
+        self.attributes.iter().try_for_each(|(location, format)| {
+            let found = layouts
+                .iter()
+                .flat_map(|layout| layout.attributes.iter())
+                .find(|attr| attr.location == *location as u64);
+
+            match found {
+                None => Err(format!(
+                    "Vertex attribute @location({}) is missing in the VertexLayout",
+                    location
+                )),
+                Some(attr) if format_kind(attr.format) != format_kind(*format) => Err(format!(
+                    "Vertex attribute @location({}) is {:?} in the shader but {:?} in the VertexLayout",
+                    location, format, attr.format
+                )),
+                _ => Ok(()),
+            }
+        })
+    }
+
+    fn check_bind_group_layout(&self, layouts: &[BindGroupLayout]) -> Result<(), String> {
+        self.groups
+            .iter()
+            .enumerate()
+            .try_for_each(|(group, bindings)| {
+                bindings.iter().try_for_each(|binding| {
+                    let found = layouts.get(group).and_then(|layout| {
+                        layout
+                            .entries
+                            .iter()
+                            .find(|entry| entry.location == binding.location)
+                    });
+
+                    let entry = found.ok_or_else(|| {
+                        format!(
+                            "Shader binding (group {}, binding {}) is missing in the BindGroupLayout",
+                            group, binding.location
+                        )
+                    })?;
+
+                    if entry.typ != binding.typ {
+                        return Err(format!(
+                            "Shader binding (group {}, binding {}) is {:?} in the shader but {:?} in the BindGroupLayout",
+                            group, binding.location, binding.typ, entry.typ
+                        ));
+                    }
+
+                    if binding.filterable && !entry.filterable {
+                        return Err(format!(
+                            "Shader binding (group {}, binding {}) is sampled in the shader but not filterable in the BindGroupLayout",
+                            group, binding.location
+                        ));
+                    }
+
+                    let missing_visibility = (binding.visible_vertex && !entry.visible_vertex)
+                        || (binding.visible_fragment && !entry.visible_fragment);
+                    if missing_visibility {
+                        return Err(format!(
+                            "Shader binding (group {}, binding {}) is not visible in all the stages using it",
+                            group, binding.location
+                        ));
+                    }
+
+                    Ok(())
+                })
+            })
+    }
+}
+
+/// Textures used by `textureSample*` functions, `None` if some of them cannot be known
+/// because the texture is passed as a function argument
+fn sampled_textures(module: &Module) -> Option<HashSet<Handle<GlobalVariable>>> {
+    let mut sampled = HashSet::new();
+    let functions = module
+        .functions
+        .iter()
+        .map(|(_, func)| func)
+        .chain(module.entry_points.iter().map(|ep| &ep.function));
+    for func in functions {
+        for (_, expr) in func.expressions.iter() {
+            if let Expression::ImageSample { image, .. } = expr {
+                match func.expressions[*image] {
+                    Expression::GlobalVariable(handle) => {
+                        sampled.insert(handle);
+                    }
+                    _ => return None,
+                }
+            }
+        }
+    }
+
+    Some(sampled)
+}
+
+fn vertex_attribute(
+    binding: &Binding,
+    inner: &TypeInner,
+) -> Result<Option<(u32, VertexFormat)>, String> {
+    let Binding::Location { location, .. } = binding else {
+        return Ok(None);
+    };
+
+    let (kind, width, size) = match *inner {
+        TypeInner::Scalar { kind, width } => (kind, width, 1),
+        TypeInner::Vector { size, kind, width } => (kind, width, size as u8),
+        _ => {
+            return Err(format!(
+                "Vertex attribute @location({}) must be a scalar or a vector",
+                location
+            ))
+        }
+    };
+
+    let format = match (kind, width, size) {
+        (ScalarKind::Float, 4, 1) => VertexFormat::Float32,
+        (ScalarKind::Float, 4, 2) => VertexFormat::Float32x2,
+        (ScalarKind::Float, 4, 3) => VertexFormat::Float32x3,
+        (ScalarKind::Float, 4, 4) => VertexFormat::Float32x4,
+        (ScalarKind::Float, 2, 2) => VertexFormat::Float16x2,
+        (ScalarKind::Float, 2, 4) => VertexFormat::Float16x4,
+        (ScalarKind::Uint, 4, 1) => VertexFormat::UInt32,
+        (ScalarKind::Uint, 4, 2) => VertexFormat::UInt32x2,
+        (ScalarKind::Uint, 4, 3) => VertexFormat::UInt32x3,
+        (ScalarKind::Uint, 4, 4) => VertexFormat::UInt32x4,
+        (ScalarKind::Sint, 4, 1) => VertexFormat::Int32,
+        (ScalarKind::Sint, 4, 2) => VertexFormat::Int32x2,
+        (ScalarKind::Sint, 4, 3) => VertexFormat::Int32x3,
+        (ScalarKind::Sint, 4, 4) => VertexFormat::Int32x4,
+        _ => {
+            return Err(format!(
+                "Vertex attribute @location({}) uses an unsupported type",
+                location
+            ))
+        }
+    };
+
+    Ok(Some((*location, format)))
+}
+
+/// Type of the values read by the shader, normalized formats are read as floats
+fn storage_texture_format(format: StorageFormat) -> Option<TextureFormat> {
+    match format {
+        StorageFormat::Rgba8Unorm => Some(TextureFormat::Rgba8Unorm),
+        StorageFormat::Rgba16Float => Some(TextureFormat::Rgba16Float),
+        StorageFormat::Rgba32Float => Some(TextureFormat::Rgba32Float),
+        _ => None,
+    }
+}
+
+fn format_kind(format: VertexFormat) -> ScalarKind {
+    use VertexFormat::*;
+    match format {
+        UInt8x2 | UInt8x4 | UInt16x2 | UInt16x4 | UInt32 | UInt32x2 | UInt32x3 | UInt32x4 => {
+            ScalarKind::Uint
+        }
+        Int8x2 | Int8x4 | Int16x2 | Int16x4 | Int32 | Int32x2 | Int32x3 | Int32x4 => {
+            ScalarKind::Sint
+        }
+        U8x2norm | U8x4norm | I8x2norm | I8x4norm | U16x2norm | U16x4norm | Int16x2norm
+        | Int16x4norm | Float16x2 | Float16x4 | Float32 | Float32x2 | Float32x3 | Float32x4 => {
+            ScalarKind::Float
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bind_group::BindType;
+    use crate::{ShaderPreprocessor, ShaderRegistry};
+
+    const SHADER: &str = r#"
+struct Transform {
+    mvp: mat4x4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> transform: Transform;
+
+@group(1) @binding(0)
+var t_texture: texture_2d<f32>;
+@group(1) @binding(1)
+var s_texture: sampler;
+
+struct VertexInput {
+    @location(1) uvs: vec2<f32>,
+    @location(0) pos: vec3<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) pos: vec4<f32>,
+    @location(0) uvs: vec2<f32>,
+};
+
+@vertex
+fn vs_main(model: VertexInput, @location(2) id: u32) -> VertexOutput {
+    var out: VertexOutput;
+    out.uvs = model.uvs;
+    out.pos = transform.mvp * vec4<f32>(model.pos, 1.0);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(t_texture, s_texture, in.uvs);
+}
+"#;
+
+    fn reflect(desc: &mut RenderPipelineDescriptor) -> Result<(), String> {
+        let registry = ShaderRegistry::default();
+        let shader = ShaderPreprocessor::new(&registry)
+            .process("main.wgsl", SHADER)
+            .unwrap();
+        let (module, info) = shader.parse().unwrap();
+        ReflectedLayout::new(&module, &info, "vs_main", "fs_main")?.apply(desc)
+    }
+
+    #[test]
+    fn test_derive_layout() {
+        let mut desc = RenderPipelineDescriptor::default();
+        reflect(&mut desc).unwrap();
+
+        assert_eq!(desc.vertex_layout.len(), 1);
+        let attrs = desc.vertex_layout[0]
+            .attributes
+            .iter()
+            .map(|attr| format!("{}:{:?}", attr.location, attr.format))
+            .collect::<Vec<_>>();
+        assert_eq!(attrs, vec!["0:Float32x3", "1:Float32x2", "2:UInt32"]);
+
+        assert_eq!(desc.bind_group_layout.len(), 2);
+        let uniform = desc.bind_group_layout[0].entries[0];
+        assert_eq!(uniform.typ, BindType::Uniform);
+        assert!(uniform.visible_vertex && !uniform.visible_fragment);
+
+        let entries = &desc.bind_group_layout[1].entries;
+        assert_eq!(entries[0].typ, BindType::Texture);
+        assert!(entries[0].filterable);
+        assert_eq!(entries[1].typ, BindType::Sampler);
+        assert!(entries[1].visible_fragment && !entries[1].visible_vertex);
+    }
+
+    #[test]
+    fn test_layout_mismatch() {
+        let mut desc = RenderPipelineDescriptor::default();
+        desc.vertex_layout.push(
+            VertexLayout::new()
+                .with_attr(0, VertexFormat::Float32x3)
+                .with_attr(1, VertexFormat::Float32x2),
+        );
+        assert_eq!(
+            reflect(&mut desc).unwrap_err(),
+            "Vertex attribute @location(2) is missing in the VertexLayout"
+        );
+
+        let mut desc = RenderPipelineDescriptor::default();
+        desc.bind_group_layout.push(
+            BindGroupLayout::new().with_entry(BindingType::texture(0).with_vertex_visibility(true)),
+        );
+        assert_eq!(
+            reflect(&mut desc).unwrap_err(),
+            "Shader binding (group 0, binding 0) is Uniform in the shader but Texture in the BindGroupLayout"
+        );
+
+        let mut desc = RenderPipelineDescriptor::default();
+        desc.bind_group_layout.push(
+            BindGroupLayout::new().with_entry(BindingType::uniform(0).with_vertex_visibility(true)),
+        );
+        desc.bind_group_layout.push(
+            BindGroupLayout::new()
+                .with_entry(
+                    BindingType::texture(0)
+                        .with_fragment_visibility(true)
+                        .with_filterable(false),
+                )
+                .with_entry(BindingType::sampler(1).with_fragment_visibility(true)),
+        );
+        assert_eq!(
+            reflect(&mut desc).unwrap_err(),
+            "Shader binding (group 1, binding 0) is sampled in the shader but not filterable in the BindGroupLayout"
+        );
+    }
+
+    #[test]
+    fn test_load_only_texture() {
+        let shader = r#"
+@group(0) @binding(0)
+var t_data: texture_2d<f32>;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(@builtin(position) pos: vec4<f32>) -> @location(0) vec4<f32> {
+    return textureLoad(t_data, vec2<i32>(pos.xy), 0);
+}
+"#;
+        let registry = ShaderRegistry::default();
+        let shader = ShaderPreprocessor::new(&registry)
+            .process("main.wgsl", shader)
+            .unwrap();
+        let (module, info) = shader.parse().unwrap();
+        let layout = ReflectedLayout::new(&module, &info, "vs_main", "fs_main").unwrap();
+
+        let mut desc = RenderPipelineDescriptor::default();
+        layout.apply(&mut desc).unwrap();
+        assert!(!desc.bind_group_layout[0].entries[0].filterable);
+
+        // a filterable layout is still valid for textureLoad
+        let mut desc = RenderPipelineDescriptor::default();
+        desc.bind_group_layout.push(
+            BindGroupLayout::new()
+                .with_entry(BindingType::texture(0).with_fragment_visibility(true)),
+        );
+        layout.apply(&mut desc).unwrap();
+    }
+
+    #[test]
+    fn test_too_many_attributes() {
+        let inputs = (0..17)
+            .map(|i| format!("@location({}) a{}: f32", i, i))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let shader = format!(
+            r#"
+@vertex
+fn vs_main({}) -> @builtin(position) vec4<f32> {{
+    return vec4<f32>(a0, a16, 0.0, 1.0);
+}}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {{
+    return vec4<f32>(1.0);
+}}
+"#,
+            inputs
+        );
+        let registry = ShaderRegistry::default();
+        let shader = ShaderPreprocessor::new(&registry)
+            .process("main.wgsl", &shader)
+            .unwrap();
+        let (module, info) = shader.parse().unwrap();
+        let layout = ReflectedLayout::new(&module, &info, "vs_main", "fs_main").unwrap();
+
+        let mut desc = RenderPipelineDescriptor::default();
+        assert_eq!(
+            layout.apply(&mut desc).unwrap_err(),
+            "The shader uses '17' vertex attributes but one vertex buffer can have up to '16', set the vertex layouts with `with_vertex_layout`"
+        );
+    }
+}
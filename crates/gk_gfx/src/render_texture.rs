@@ -8,6 +8,8 @@ pub trait GKRenderTexture {
     fn id(&self) -> RenderTextureId;
     fn texture(&self) -> &Texture;
     fn into_inner(self) -> Texture;
+    /// MSAA samples used to render to the texture
+    fn samples(&self) -> u32;
 }
 
 #[derive(Debug, Default, Copy, Clone)]
@@ -16,4 +18,41 @@ pub struct RenderTextureDescriptor<'a> {
     pub depth: bool,
     pub width: u32,
     pub height: u32,
+    /// MSAA samples, the result is resolved into the texture
+    pub samples: u32,
+}
+
+/// Sample counts supported by all the adapters
+pub(crate) fn validate_samples(samples: u32) -> Result<(), String> {
+    if matches!(samples, 1 | 4) {
+        Ok(())
+    } else {
+        Err(format!("MSAA samples must be 1 or 4, got '{}'", samples))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_utils::headless_gfx;
+    use crate::*;
+
+    #[test]
+    fn test_render_texture_samples() {
+        let mut gfx = headless_gfx();
+
+        let rt = gfx
+            .create_render_texture()
+            .with_size(10, 10)
+            .with_samples(4)
+            .build()
+            .unwrap();
+        assert_eq!(rt.samples(), 4);
+
+        assert!(gfx
+            .create_render_texture()
+            .with_size(10, 10)
+            .with_samples(3)
+            .build()
+            .is_err());
+    }
 }
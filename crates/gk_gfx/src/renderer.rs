@@ -1,8 +1,12 @@
 use crate::color::Color;
 use crate::consts::{
-    MAX_BIND_GROUPS_PER_PIPELINE, MAX_UNIFORM_BUFFERS_PER_SHADER_STAGE, MAX_VERTEX_BUFFERS,
+    MAX_BIND_GROUPS_PER_PIPELINE, MAX_DYNAMIC_UNIFORM_BUFFERS_PER_PIPELINE,
+    MAX_UNIFORM_BUFFERS_PER_SHADER_STAGE, MAX_VERTEX_BUFFERS, UNIFORM_BUFFER_OFFSET_ALIGNMENT,
+};
+use crate::{
+    BindGroup, Buffer, BufferUsage, ClearOptions, ComputePipeline, GKBuffer, GKRenderPipeline,
+    IndexFormat, RenderPipeline, TextureRect,
 };
-use crate::{BindGroup, Buffer, ClearOptions, RenderPipeline};
 use arrayvec::ArrayVec;
 use gk_sys::event::DrawEvent;
 use std::ops::Range;
@@ -12,21 +16,67 @@ use std::ops::Range;
 
 const MAX_BUFFERS: usize = MAX_VERTEX_BUFFERS + MAX_UNIFORM_BUFFERS_PER_SHADER_STAGE + 1;
 
+/// Area of the target where the vertices are mapped, in pixels
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Viewport {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub min_depth: f32,
+    pub max_depth: f32,
+}
+
 #[derive(Default)]
 pub(crate) struct RPassVertices {
     pub(crate) range: Range<u32>,
+    pub(crate) base_vertex: i32,
+    /// Set by `draw_indexed_base`, it needs an Index buffer
+    pub(crate) indexed: bool,
     pub(crate) instances: Option<u32>,
+    pub(crate) viewport: Option<Viewport>,
+    pub(crate) scissor: Option<TextureRect>,
+}
+
+/// Dynamic offsets of a bind group used from the draw `draw` onwards
+pub(crate) struct RPassOffsets {
+    pub(crate) draw: usize,
+    pub(crate) group: usize,
+    /// Range inside `RenderPass::offsets`
+    pub(crate) range: Range<usize>,
+}
+
+/// Buffer bound to a RenderPass, `size: None` means until the end of the buffer
+#[derive(Copy, Clone)]
+pub(crate) struct RPassBuffer<'a> {
+    pub(crate) buffer: &'a Buffer,
+    pub(crate) offset: u64,
+    pub(crate) size: Option<u64>,
+}
+
+impl<'a> RPassBuffer<'a> {
+    /// Range of bytes used
+    pub(crate) fn range(&self) -> Range<u64> {
+        let end = self.size.map_or(self.buffer.len() as u64, |size| {
+            self.offset.saturating_add(size)
+        });
+        self.offset..end
+    }
 }
 
 #[derive(Default)]
 pub struct RenderPass<'a> {
     pub(crate) size: Option<(u32, u32)>,
     pub(crate) pipeline: Option<&'a RenderPipeline>,
-    pub(crate) buffers: ArrayVec<&'a Buffer, MAX_BUFFERS>,
+    pub(crate) buffers: ArrayVec<RPassBuffer<'a>, MAX_BUFFERS>,
     pub(crate) clear_options: ClearOptions,
     pub(crate) vertices: Vec<RPassVertices>,
     pub(crate) bind_groups: ArrayVec<&'a BindGroup, MAX_BIND_GROUPS_PER_PIPELINE>,
     pub(crate) stencil_ref: Option<u8>,
+    pub(crate) viewport: Option<Viewport>,
+    pub(crate) scissor: Option<TextureRect>,
+    pub(crate) offsets: Vec<u32>,
+    pub(crate) offset_changes: Vec<RPassOffsets>,
 }
 
 impl<'a> RenderPass<'a> {
@@ -65,7 +115,27 @@ impl<'a> RenderPass<'a> {
     }
 
     pub fn buffers(&mut self, buffers: &[&'a Buffer]) -> &mut Self {
-        self.buffers.try_extend_from_slice(buffers).unwrap();
+        buffers.iter().for_each(|buffer| {
+            self.buffer_range(buffer, 0, None);
+        });
+        self
+    }
+
+    /// Binds only a part of the buffer, `size: None` means until the end of the buffer
+    /// The offset must be a multiple of 4
+    pub fn buffer_range(
+        &mut self,
+        buffer: &'a Buffer,
+        offset: u64,
+        size: Option<u64>,
+    ) -> &mut Self {
+        self.buffers
+            .try_push(RPassBuffer {
+                buffer,
+                offset,
+                size,
+            })
+            .unwrap();
         self
     }
 
@@ -74,26 +144,135 @@ impl<'a> RenderPass<'a> {
         self
     }
 
-    pub fn draw(&mut self, vertices: Range<u32>) -> &mut Self {
-        self.vertices.push(RPassVertices {
-            range: vertices,
-            instances: None,
+    /// Offsets for the dynamic uniforms of the bind group at `index`, used by the next draws
+    /// Each offset must be a multiple of 256
+    pub fn dynamic_offsets(&mut self, index: usize, offsets: &[u32]) -> &mut Self {
+        let start = self.offsets.len();
+        self.offsets.extend_from_slice(offsets);
+        self.offset_changes.push(RPassOffsets {
+            draw: self.vertices.len(),
+            group: index,
+            range: start..self.offsets.len(),
+        });
+        self
+    }
+
+    /// Dynamic offsets set right before the draw `draw`
+    pub(crate) fn offset_changes_at(&self, draw: usize) -> &[RPassOffsets] {
+        let start = self.offset_changes.partition_point(|c| c.draw < draw);
+        let end = self.offset_changes.partition_point(|c| c.draw <= draw);
+        &self.offset_changes[start..end]
+    }
+
+    /// Used by the next draws, by default it's the whole target
+    pub fn viewport(
+        &mut self,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        min_depth: f32,
+        max_depth: f32,
+    ) -> &mut Self {
+        self.viewport = Some(Viewport {
+            x,
+            y,
+            width,
+            height,
+            min_depth,
+            max_depth,
         });
         self
     }
 
+    /// Pixels outside the rect are discarded by the next draws
+    pub fn scissor(&mut self, x: u32, y: u32, width: u32, height: u32) -> &mut Self {
+        self.scissor = Some(TextureRect {
+            x,
+            y,
+            width,
+            height,
+        });
+        self
+    }
+
+    pub fn draw(&mut self, vertices: Range<u32>) -> &mut Self {
+        self.push_vertices(vertices, 0, false, None)
+    }
+
     pub fn draw_instanced(&mut self, vertices: Range<u32>, instances: u32) -> &mut Self {
+        self.push_vertices(vertices, 0, false, Some(instances))
+    }
+
+    /// Draws the range of indices adding `base_vertex` to each index before reading the vertex buffer
+    /// `Gfx::render` returns an error if there is no Index buffer bound
+    pub fn draw_indexed_base(
+        &mut self,
+        indices: Range<u32>,
+        base_vertex: i32,
+        instances: u32,
+    ) -> &mut Self {
+        self.push_vertices(indices, base_vertex, true, Some(instances))
+    }
+
+    fn push_vertices(
+        &mut self,
+        range: Range<u32>,
+        base_vertex: i32,
+        indexed: bool,
+        instances: Option<u32>,
+    ) -> &mut Self {
         self.vertices.push(RPassVertices {
-            range: vertices,
-            instances: Some(instances),
+            range,
+            base_vertex,
+            indexed,
+            instances,
+            viewport: self.viewport,
+            scissor: self.scissor,
         });
         self
     }
 }
 
+#[derive(Default)]
+pub struct ComputePass<'a> {
+    pub(crate) pipeline: Option<&'a ComputePipeline>,
+    pub(crate) bind_groups: ArrayVec<&'a BindGroup, MAX_BIND_GROUPS_PER_PIPELINE>,
+    pub(crate) dispatches: Vec<(u32, u32, u32)>,
+}
+
+impl<'a> ComputePass<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn pipeline(&mut self, pipeline: &'a ComputePipeline) -> &mut Self {
+        self.pipeline = Some(pipeline);
+        self
+    }
+
+    pub fn bindings(&mut self, groups: &[&'a BindGroup]) -> &mut Self {
+        self.bind_groups.try_extend_from_slice(groups).unwrap();
+        self
+    }
+
+    /// Run the number of workgroups set on each axis
+    pub fn dispatch(&mut self, x: u32, y: u32, z: u32) -> &mut Self {
+        self.dispatches.push((x, y, z));
+        self
+    }
+}
+
+/// RenderPasses are boxed because they are much bigger than ComputePasses
+pub(crate) enum Pass<'a> {
+    Render(Box<RenderPass<'a>>),
+    Compute(ComputePass<'a>),
+}
+
+/// Passes are executed in the same order they are added
 #[derive(Default)]
 pub struct Renderer<'a> {
-    pub(crate) passes: Vec<RenderPass<'a>>,
+    pub(crate) passes: Vec<Pass<'a>>,
 }
 
 impl<'a> Renderer<'a> {
@@ -102,11 +281,289 @@ impl<'a> Renderer<'a> {
     }
 
     pub fn add_pass(&mut self, rpass: RenderPass<'a>) {
-        self.passes.push(rpass);
+        self.passes.push(Pass::Render(Box::new(rpass)));
     }
 
     pub fn begin_pass(&mut self) -> &mut RenderPass<'a> {
-        self.passes.push(RenderPass::default());
-        self.passes.last_mut().unwrap()
+        self.passes.push(Pass::Render(Box::default()));
+        match self.passes.last_mut() {
+            Some(Pass::Render(rpass)) => rpass,
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn add_compute_pass(&mut self, cpass: ComputePass<'a>) {
+        self.passes.push(Pass::Compute(cpass));
+    }
+
+    pub fn begin_compute_pass(&mut self) -> &mut ComputePass<'a> {
+        self.passes.push(Pass::Compute(ComputePass::default()));
+        match self.passes.last_mut() {
+            Some(Pass::Compute(cpass)) => cpass,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Checks the values that would make the backend panic
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        // compute passes cannot set dynamic offsets
+        let dynamic_group = self.passes.iter().find_map(|pass| match pass {
+            Pass::Compute(cpass) => cpass
+                .bind_groups
+                .iter()
+                .position(|bg| !bg.dynamic_bindings.is_empty()),
+            Pass::Render(_) => None,
+        });
+        if let Some(group) = dynamic_group {
+            return Err(format!(
+                "The bind group '{}' uses dynamic offsets, they are not supported on compute passes",
+                group
+            ));
+        }
+
+        self.passes
+            .iter()
+            .filter_map(|pass| match pass {
+                Pass::Render(rpass) => Some(rpass),
+                Pass::Compute(_) => None,
+            })
+            .try_for_each(|rpass| {
+                // vertex buffers offsets must be 4-aligned and index ones aligned to the format
+                let index_format = rpass
+                    .pipeline
+                    .map_or(IndexFormat::default(), |pip| pip.index_format());
+                rpass.buffers.iter().try_for_each(|rb| {
+                    let range = rb.range();
+                    let align = match rb.buffer.usage() {
+                        BufferUsage::Index => index_format.bytes(),
+                        _ => 4,
+                    };
+                    let valid_offset = rb.offset.is_multiple_of(align);
+                    let valid_range = !range.is_empty() && range.end <= rb.buffer.len() as u64;
+                    if valid_offset && valid_range {
+                        Ok(())
+                    } else {
+                        Err(format!(
+                            "Invalid range '{:?}' for buffer '{:?}' with size '{}'",
+                            range,
+                            rb.buffer.id(),
+                            rb.buffer.len()
+                        ))
+                    }
+                })?;
+
+                let has_index_buffer = rpass
+                    .buffers
+                    .iter()
+                    .any(|rb| matches!(rb.buffer.usage(), BufferUsage::Index));
+                if !has_index_buffer && rpass.vertices.iter().any(|vertices| vertices.indexed) {
+                    return Err("Indexed draws need an Index buffer".to_string());
+                }
+
+                rpass.offset_changes.iter().try_for_each(|change| {
+                    if change.group >= rpass.bind_groups.len() {
+                        return Err(format!(
+                            "Dynamic offsets set for the bind group '{}' but only '{}' are bound",
+                            change.group,
+                            rpass.bind_groups.len()
+                        ));
+                    }
+
+                    if change.range.len() > MAX_DYNAMIC_UNIFORM_BUFFERS_PER_PIPELINE {
+                        return Err(format!(
+                            "Cannot use more than {} dynamic offsets per bind group",
+                            MAX_DYNAMIC_UNIFORM_BUFFERS_PER_PIPELINE
+                        ));
+                    }
+
+                    let bindings = &rpass.bind_groups[change.group].dynamic_bindings;
+                    if change.range.len() != bindings.len() {
+                        return Err(format!(
+                            "The bind group '{}' needs {} dynamic offsets but {} were set",
+                            change.group,
+                            bindings.len(),
+                            change.range.len()
+                        ));
+                    }
+
+                    rpass.offsets[change.range.clone()]
+                        .iter()
+                        .zip(bindings.iter())
+                        .try_for_each(|(&offset, binding)| {
+                            if !offset.is_multiple_of(UNIFORM_BUFFER_OFFSET_ALIGNMENT) {
+                                return Err(format!(
+                                    "Dynamic offset '{}' must be a multiple of {}",
+                                    offset, UNIFORM_BUFFER_OFFSET_ALIGNMENT
+                                ));
+                            }
+
+                            if offset as u64 + binding.size > binding.buffer_size {
+                                return Err(format!(
+                                    "Dynamic offset '{}' with a binding of '{}' bytes is out of the buffer of '{}' bytes",
+                                    offset, binding.size, binding.buffer_size
+                                ));
+                            }
+
+                            Ok(())
+                        })
+                })?;
+
+                // the groups with dynamic uniforms need their offsets before the first draw
+                if !rpass.vertices.is_empty() {
+                    let first_changes = rpass.offset_changes_at(0);
+                    let missing = rpass.bind_groups.iter().enumerate().find(|(group, bg)| {
+                        !bg.dynamic_bindings.is_empty()
+                            && !first_changes.iter().any(|change| change.group == *group)
+                    });
+                    if let Some((group, bg)) = missing {
+                        return Err(format!(
+                            "The bind group '{}' needs {} dynamic offsets but none were set",
+                            group,
+                            bg.dynamic_bindings.len()
+                        ));
+                    }
+                }
+
+                rpass
+                    .vertices
+                    .iter()
+                    .filter_map(|vertices| vertices.viewport)
+                    .try_for_each(|vp| {
+                        let valid_size = vp.width > 0.0 && vp.height > 0.0;
+                        let valid_depth = (0.0..=1.0).contains(&vp.min_depth)
+                            && (0.0..=1.0).contains(&vp.max_depth)
+                            && vp.min_depth <= vp.max_depth;
+                        if valid_size && valid_depth {
+                            Ok(())
+                        } else {
+                            Err(format!("Invalid viewport '{:?}'", vp))
+                        }
+                    })
+            })
+    }
+
+    pub(crate) fn has_render_passes(&self) -> bool {
+        self.passes
+            .iter()
+            .any(|pass| matches!(pass, Pass::Render(_)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_utils::headless_gfx;
+    use crate::*;
+
+    #[test]
+    fn test_record_viewport_scissor() {
+        let mut gfx = headless_gfx();
+
+        let rt = gfx
+            .create_render_texture()
+            .with_size(10, 10)
+            .build()
+            .unwrap();
+
+        let mut renderer = Renderer::new();
+        renderer
+            .begin_pass()
+            .draw(0..3)
+            .viewport(0.0, 0.0, 5.0, 5.0, 0.0, 1.0)
+            .scissor(1, 1, 4, 4)
+            .draw(0..6);
+        gfx.render(&rt, &renderer).unwrap();
+
+        let draws = &gfx.recorded_passes()[0].draws;
+        assert_eq!(draws[0].viewport, None);
+        assert_eq!(draws[0].scissor, None);
+        assert_eq!(
+            draws[1].viewport,
+            Some(Viewport {
+                x: 0.0,
+                y: 0.0,
+                width: 5.0,
+                height: 5.0,
+                min_depth: 0.0,
+                max_depth: 1.0,
+            })
+        );
+        assert_eq!(
+            draws[1].scissor,
+            Some(TextureRect {
+                x: 1,
+                y: 1,
+                width: 4,
+                height: 4,
+            })
+        );
+
+        let mut renderer = Renderer::new();
+        renderer
+            .begin_pass()
+            .viewport(0.0, 0.0, 5.0, 5.0, 0.5, 0.2)
+            .draw(0..3);
+        assert!(gfx.render(&rt, &renderer).is_err());
+    }
+
+    #[test]
+    fn test_record_buffer_ranges() {
+        let mut gfx = headless_gfx();
+
+        let rt = gfx
+            .create_render_texture()
+            .with_size(10, 10)
+            .build()
+            .unwrap();
+        let vbo = gfx.create_vertex_buffer(&[0.0f32; 12]).build().unwrap();
+        let ebo = gfx.create_index_buffer(&[0u16; 6]).build().unwrap();
+
+        let mut renderer = Renderer::new();
+        renderer
+            .begin_pass()
+            .buffer_range(&vbo, 24, None)
+            .buffer_range(&ebo, 4, Some(8))
+            .draw_indexed_base(0..3, 2, 1);
+        gfx.render(&rt, &renderer).unwrap();
+
+        let pass = &gfx.recorded_passes()[0];
+        assert_eq!(pass.buffers[0].offset, 24);
+        assert_eq!(pass.buffers[1].size, Some(8));
+        assert_eq!(pass.draws[0].base_vertex, 2);
+        assert_eq!(pass.draws[0].instances, Some(1));
+
+        let mut renderer = Renderer::new();
+        renderer.begin_pass().buffer_range(&vbo, 2, None).draw(0..3);
+        assert!(gfx.render(&rt, &renderer).is_err());
+
+        let mut renderer = Renderer::new();
+        renderer
+            .begin_pass()
+            .buffer_range(&ebo, 8, Some(8))
+            .draw(0..3);
+        assert!(gfx.render(&rt, &renderer).is_err());
+
+        // UInt16 indices only need 2-aligned offsets
+        let pip = gfx
+            .create_render_pipeline("")
+            .with_index_format(IndexFormat::UInt16)
+            .build()
+            .unwrap();
+        let mut renderer = Renderer::new();
+        renderer
+            .begin_pass()
+            .pipeline(&pip)
+            .buffer_range(&ebo, 2, None)
+            .draw_indexed_base(0..3, 0, 1);
+        gfx.render(&rt, &renderer).unwrap();
+
+        let mut renderer = Renderer::new();
+        renderer
+            .begin_pass()
+            .buffers(&[&vbo])
+            .draw_indexed_base(0..3, 2, 1);
+        assert_eq!(
+            gfx.render(&rt, &renderer).unwrap_err(),
+            "Indexed draws need an Index buffer"
+        );
     }
 }
@@ -0,0 +1,335 @@
+use hashbrown::HashMap;
+use naga::valid::{Capabilities, ModuleInfo, ValidationFlags, Validator};
+use naga::{Module, SourceLocation};
+
+/// Named WGSL sources that can be used with `#include "name"`
+#[derive(Debug, Default, Clone)]
+pub struct ShaderRegistry {
+    sources: HashMap<String, String>,
+}
+
+impl ShaderRegistry {
+    pub fn add(&mut self, name: &str, source: &str) -> &mut Self {
+        self.sources.insert(name.to_string(), source.to_string());
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.sources.get(name).map(|s| s.as_str())
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        self.sources.remove(name)
+    }
+}
+
+/// Resolves `#include`, `#define`, `#undef`, `#ifdef`, `#ifndef`, `#else` and `#endif`
+/// directives. Each file is included only once, like with `#pragma once` any other
+/// `#include` of the same file is skipped, even if the defines changed since then
+pub struct ShaderPreprocessor<'a> {
+    registry: &'a ShaderRegistry,
+    defines: HashMap<String, String>,
+}
+
+/// WGSL code resulting of the preprocessor, it keeps the origin of each line
+#[derive(Debug, Clone)]
+pub struct ProcessedShader {
+    code: String,
+    files: Vec<String>,
+    lines: Vec<(usize, usize)>,
+}
+
+struct Branch {
+    active: bool,
+    parent_active: bool,
+    has_else: bool,
+    line: usize,
+}
+
+impl<'a> ShaderPreprocessor<'a> {
+    pub fn new(registry: &'a ShaderRegistry) -> Self {
+        Self {
+            registry,
+            defines: HashMap::default(),
+        }
+    }
+
+    /// Set a define before processing, an empty value can be used for `#ifdef` flags
+    pub fn with_define(mut self, name: &str, value: &str) -> Self {
+        self.defines.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// `name` is used to identify the source in errors
+    pub fn process(mut self, name: &str, source: &str) -> Result<ProcessedShader, String> {
+        let mut shader = ProcessedShader {
+            code: String::with_capacity(source.len()),
+            files: vec![],
+            lines: vec![],
+        };
+        // the files already included, and the main one, are never included again
+        let mut included = vec![name.to_string()];
+        self.process_file(name, source, &mut shader, &mut included)?;
+        Ok(shader)
+    }
+
+    fn process_file(
+        &mut self,
+        name: &str,
+        source: &str,
+        shader: &mut ProcessedShader,
+        included: &mut Vec<String>,
+    ) -> Result<(), String> {
+        let file_index = shader.files.len();
+        shader.files.push(name.to_string());
+
+        let mut branches: Vec<Branch> = vec![];
+        for (i, line) in source.lines().enumerate() {
+            let line_number = i + 1;
+            let err = |msg: String| format!("{}:{}: {}", name, line_number, msg);
+            let active = branches.last().is_none_or(|b| b.active);
+
+            let trimmed = line.trim();
+            let Some(directive) = trimmed.strip_prefix('#') else {
+                if active {
+                    shader.push_line(&self.replace_defines(line), file_index, line_number);
+                }
+                continue;
+            };
+
+            let (cmd, args) = directive
+                .split_once(char::is_whitespace)
+                .map(|(cmd, args)| (cmd, args.trim()))
+                .unwrap_or((directive, ""));
+
+            match cmd {
+                "ifdef" | "ifndef" => {
+                    let ident = parse_ident(args).map_err(err)?;
+                    let defined = self.defines.contains_key(ident);
+                    branches.push(Branch {
+                        active: active && (defined == (cmd == "ifdef")),
+                        parent_active: active,
+                        has_else: false,
+                        line: line_number,
+                    });
+                }
+                "else" => {
+                    let branch = branches
+                        .last_mut()
+                        .ok_or_else(|| err("#else without #ifdef".to_string()))?;
+                    if branch.has_else {
+                        return Err(err("Duplicated #else".to_string()));
+                    }
+                    branch.has_else = true;
+                    branch.active = branch.parent_active && !branch.active;
+                }
+                "endif" => {
+                    branches
+                        .pop()
+                        .ok_or_else(|| err("#endif without #ifdef".to_string()))?;
+                }
+                _ if !active => {}
+                "define" => {
+                    let (ident, value) = args
+                        .split_once(char::is_whitespace)
+                        .map(|(ident, value)| (ident, value.trim()))
+                        .unwrap_or((args, ""));
+                    let ident = parse_ident(ident).map_err(err)?;
+                    self.defines.insert(ident.to_string(), value.to_string());
+                }
+                "undef" => {
+                    let ident = parse_ident(args).map_err(err)?;
+                    self.defines.remove(ident);
+                }
+                "include" => {
+                    let include = args
+                        .strip_prefix('"')
+                        .and_then(|s| s.strip_suffix('"'))
+                        .filter(|s| !s.is_empty())
+                        .ok_or_else(|| err(format!("Invalid #include '{}'", args)))?;
+
+                    if included.iter().any(|n| n == include) {
+                        continue;
+                    }
+
+                    let include_source = self
+                        .registry
+                        .get(include)
+                        .ok_or_else(|| err(format!("Shader include '{}' not found", include)))?;
+                    included.push(include.to_string());
+                    self.process_file(include, include_source, shader, included)?;
+                }
+                _ => return Err(err(format!("Unknown directive '#{}'", cmd))),
+            }
+        }
+
+        match branches.last() {
+            Some(branch) => Err(format!("{}:{}: Unterminated #ifdef", name, branch.line)),
+            None => Ok(()),
+        }
+    }
+
+    fn replace_defines(&self, line: &str) -> String {
+        if self.defines.values().all(|v| v.is_empty()) {
+            return line.to_string();
+        }
+
+        let mut out = String::with_capacity(line.len());
+        let mut ident_start = None;
+        for (i, c) in line.char_indices() {
+            let is_ident = c.is_alphanumeric() || c == '_';
+            match (ident_start, is_ident) {
+                (None, true) => ident_start = Some(i),
+                (Some(start), false) => {
+                    out.push_str(self.resolve(&line[start..i]));
+                    out.push(c);
+                    ident_start = None;
+                }
+                (None, false) => out.push(c),
+                _ => {}
+            }
+        }
+
+        if let Some(start) = ident_start {
+            out.push_str(self.resolve(&line[start..]));
+        }
+
+        out
+    }
+
+    fn resolve<'b>(&'b self, ident: &'b str) -> &'b str {
+        match self.defines.get(ident) {
+            Some(value) if !value.is_empty() => value,
+            _ => ident,
+        }
+    }
+}
+
+impl ProcessedShader {
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Returns the file and line (1-based) where the line of the processed code comes from
+    pub fn source_location(&self, line: usize) -> Option<(&str, usize)> {
+        let (file, line) = self.lines.get(line.checked_sub(1)?)?;
+        Some((&self.files[*file], *line))
+    }
+
+    /// Parse and validate the code returning the errors with the original file and line
+    pub fn validate(&self) -> Result<(), String> {
+        self.parse().map(|_| ())
+    }
+
+    pub(crate) fn parse(&self) -> Result<(Module, ModuleInfo), String> {
+        let module = naga::front::wgsl::parse_str(&self.code).map_err(|err| {
+            let location = err.location(&self.code);
+            self.format_error(location, err.message(), || err.emit_to_string(&self.code))
+        })?;
+
+        let info = Validator::new(ValidationFlags::all(), Capabilities::all())
+            .validate(&module)
+            .map_err(|err| {
+                let location = err.location(&self.code);
+                let msg = err.as_inner().to_string();
+                self.format_error(location, &msg, || msg.clone())
+            })?;
+
+        Ok((module, info))
+    }
+
+    fn format_error(
+        &self,
+        location: Option<SourceLocation>,
+        msg: &str,
+        fallback: impl FnOnce() -> String,
+    ) -> String {
+        match location.and_then(|loc| self.source_location(loc.line_number as usize)) {
+            Some((file, line)) => format!("{}:{}: {}", file, line, msg),
+            None => fallback(),
+        }
+    }
+
+    fn push_line(&mut self, line: &str, file: usize, line_number: usize) {
+        self.code.push_str(line);
+        self.code.push('\n');
+        self.lines.push((file, line_number));
+    }
+}
+
+fn parse_ident(text: &str) -> Result<&str, String> {
+    let valid = !text.is_empty()
+        && !text.starts_with(|c: char| c.is_ascii_digit())
+        && text.chars().all(|c| c.is_alphanumeric() || c == '_');
+
+    if valid {
+        Ok(text)
+    } else {
+        Err(format!("Invalid identifier '{}'", text))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_include_and_defines() {
+        let mut registry = ShaderRegistry::default();
+        registry.add(
+            "common.wgsl",
+            "struct Transform {\n    mvp: mat4x4<f32>,\n};",
+        );
+        registry.add(
+            "lights.wgsl",
+            "#include \"common.wgsl\"\nconst LIGHTS: u32 = MAX;",
+        );
+
+        let source = "#include \"common.wgsl\"\n#include \"lights.wgsl\"\n#define MAX 4u\n#ifdef TEXTURED\nTEXTURED\n#else\nconst TEXTURED: bool = false;\n#endif";
+        let shader = ShaderPreprocessor::new(&registry)
+            .with_define("MAX", "2u")
+            .process("main.wgsl", source)
+            .unwrap();
+
+        assert_eq!(
+            shader.code(),
+            "struct Transform {\n    mvp: mat4x4<f32>,\n};\nconst LIGHTS: u32 = 2u;\nconst TEXTURED: bool = false;\n"
+        );
+        assert_eq!(shader.source_location(4), Some(("lights.wgsl", 2)));
+        assert_eq!(shader.source_location(5), Some(("main.wgsl", 7)));
+        assert!(shader.validate().is_ok());
+    }
+
+    #[test]
+    fn test_errors() {
+        let registry = ShaderRegistry::default();
+        let process = |source: &str| {
+            ShaderPreprocessor::new(&registry)
+                .process("main.wgsl", source)
+                .unwrap_err()
+        };
+
+        assert_eq!(
+            process("\n#include \"missing.wgsl\""),
+            "main.wgsl:2: Shader include 'missing.wgsl' not found"
+        );
+        assert_eq!(process("#ifdef A\n"), "main.wgsl:1: Unterminated #ifdef");
+        assert_eq!(process("#endif"), "main.wgsl:1: #endif without #ifdef");
+        assert_eq!(
+            process("#pragma once"),
+            "main.wgsl:1: Unknown directive '#pragma'"
+        );
+    }
+
+    #[test]
+    fn test_validate_maps_lines() {
+        let mut registry = ShaderRegistry::default();
+        registry.add("common.wgsl", "const A: f32 = 1.0;\nconst B: f32 = ;");
+
+        let shader = ShaderPreprocessor::new(&registry)
+            .process("main.wgsl", "#define X 1\n#include \"common.wgsl\"")
+            .unwrap();
+        let err = shader.validate().unwrap_err();
+        assert!(err.starts_with("common.wgsl:2:"), "{}", err);
+    }
+}
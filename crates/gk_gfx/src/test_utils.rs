@@ -0,0 +1,10 @@
+use crate::{Gfx, GfxAttributes};
+
+/// Gfx without a window, the tests record the commands on the empty backend
+pub(crate) fn headless_gfx() -> Gfx {
+    Gfx::new(GfxAttributes {
+        headless: true,
+        ..Default::default()
+    })
+    .unwrap()
+}
@@ -9,6 +9,9 @@ pub trait GKTexture {
 
     fn width(&self) -> u32;
     fn height(&self) -> u32;
+    fn mip_levels(&self) -> u32;
+    fn format(&self) -> TextureFormat;
+    fn is_writable(&self) -> bool;
 }
 
 #[derive(Debug, Default, Copy, Clone)]
@@ -16,15 +19,113 @@ pub struct TextureDescriptor<'a> {
     pub label: Option<&'a str>,
     pub format: TextureFormat,
     pub write: bool,
+    pub storage: bool,
+    /// Generate the mip chain from the first level
+    pub mipmaps: bool,
 }
 
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 pub enum TextureFormat {
     #[default]
     Rgba8UnormSrgb,
     Bgra8UnormSrgb,
+    /// Linear format that can be used by storage textures
+    Rgba8Unorm,
+    /// Single channel, useful for font atlases and masks
+    R8Unorm,
+    Rgba16Float,
+    /// It's not filterable, use it as storage texture or read it with `textureLoad`
+    /// using a `BindingType::texture(..).with_filterable(false)` binding
+    Rgba32Float,
     // Depth16,
     Depth32Float,
+    Depth24PlusStencil8,
+    // - Compressed formats, they need the adapter's support
+    Bc1RgbaUnorm,
+    Bc1RgbaUnormSrgb,
+    Bc3RgbaUnorm,
+    Bc3RgbaUnormSrgb,
+    Bc4RUnorm,
+    Bc5RgUnorm,
+    Bc7RgbaUnorm,
+    Bc7RgbaUnormSrgb,
+    Etc2Rgb8Unorm,
+    Etc2Rgb8UnormSrgb,
+    Etc2Rgba8Unorm,
+    Etc2Rgba8UnormSrgb,
+    Astc4x4Unorm,
+    Astc4x4UnormSrgb,
+}
+
+impl TextureFormat {
+    /// Size in pixels of each block, uncompressed formats use blocks of one pixel
+    pub fn block_dimensions(&self) -> (u32, u32) {
+        if self.is_compressed() {
+            (4, 4)
+        } else {
+            (1, 1)
+        }
+    }
+
+    /// Bytes used by each block, or each pixel for uncompressed formats
+    pub fn block_bytes(&self) -> u32 {
+        use TextureFormat::*;
+        match self {
+            R8Unorm => 1,
+            Rgba8UnormSrgb | Bgra8UnormSrgb | Rgba8Unorm | Depth32Float | Depth24PlusStencil8 => 4,
+            Rgba16Float => 8,
+            Rgba32Float => 16,
+            Bc1RgbaUnorm | Bc1RgbaUnormSrgb | Bc4RUnorm | Etc2Rgb8Unorm | Etc2Rgb8UnormSrgb => 8,
+            Bc3RgbaUnorm | Bc3RgbaUnormSrgb | Bc5RgUnorm | Bc7RgbaUnorm | Bc7RgbaUnormSrgb
+            | Etc2Rgba8Unorm | Etc2Rgba8UnormSrgb | Astc4x4Unorm | Astc4x4UnormSrgb => 16,
+        }
+    }
+
+    pub fn is_compressed(&self) -> bool {
+        use TextureFormat::*;
+        matches!(
+            self,
+            Bc1RgbaUnorm
+                | Bc1RgbaUnormSrgb
+                | Bc3RgbaUnorm
+                | Bc3RgbaUnormSrgb
+                | Bc4RUnorm
+                | Bc5RgUnorm
+                | Bc7RgbaUnorm
+                | Bc7RgbaUnormSrgb
+                | Etc2Rgb8Unorm
+                | Etc2Rgb8UnormSrgb
+                | Etc2Rgba8Unorm
+                | Etc2Rgba8UnormSrgb
+                | Astc4x4Unorm
+                | Astc4x4UnormSrgb
+        )
+    }
+
+    pub fn is_depth(&self) -> bool {
+        matches!(
+            self,
+            TextureFormat::Depth32Float | TextureFormat::Depth24PlusStencil8
+        )
+    }
+
+    pub fn has_stencil(&self) -> bool {
+        matches!(self, TextureFormat::Depth24PlusStencil8)
+    }
+
+    /// Bytes of each row of blocks for the given width, `None` if it overflows
+    pub fn bytes_per_row(&self, width: u32) -> Option<u32> {
+        width
+            .div_ceil(self.block_dimensions().0)
+            .checked_mul(self.block_bytes())
+    }
+
+    /// Bytes needed to store an image of the given size, `None` if it overflows
+    pub fn data_size(&self, width: u32, height: u32) -> Option<usize> {
+        let rows = height.div_ceil(self.block_dimensions().1);
+        let size = (self.bytes_per_row(width)? as u64).checked_mul(rows as u64)?;
+        usize::try_from(size).ok()
+    }
 }
 
 #[derive(Debug, Default, Copy, Clone)]
@@ -32,6 +133,117 @@ pub struct TextureData<'a> {
     pub bytes: &'a [u8],
     pub width: u32,
     pub height: u32,
+    /// Explicit mip levels after the first one, each half the size of the previous one
+    pub mips: &'a [&'a [u8]],
+}
+
+/// Number of levels of a full mip chain for the given size
+pub fn mip_level_count(width: u32, height: u32) -> u32 {
+    32 - width.max(height).max(1).leading_zeros()
+}
+
+/// Size in pixels of the mip level
+pub fn mip_level_size(width: u32, height: u32, level: u32) -> (u32, u32) {
+    ((width >> level).max(1), (height >> level).max(1))
+}
+
+impl TextureData<'_> {
+    /// Number of mip levels of the texture for the descriptor
+    pub(crate) fn mip_levels(&self, desc: &TextureDescriptor) -> u32 {
+        if desc.mipmaps {
+            mip_level_count(self.width, self.height)
+        } else {
+            1 + self.mips.len() as u32
+        }
+    }
+
+    /// Checks the size of the data and the mip levels against the descriptor
+    pub(crate) fn validate(&self, desc: &TextureDescriptor) -> Result<(), String> {
+        let format = desc.format;
+        let (block_width, block_height) = format.block_dimensions();
+        if !self.width.is_multiple_of(block_width) || !self.height.is_multiple_of(block_height) {
+            return Err(format!(
+                "Texture with format '{:?}' must have a size multiple of '{:?}', got '{:?}'",
+                format,
+                (block_width, block_height),
+                (self.width, self.height)
+            ));
+        }
+
+        let expected = format.data_size(self.width, self.height).ok_or_else(|| {
+            format!(
+                "Texture with size '{:?}' is too big",
+                (self.width, self.height)
+            )
+        })?;
+        if !self.bytes.is_empty() && self.bytes.len() != expected {
+            return Err(format!(
+                "Texture with size '{:?}' and format '{:?}' expects '{}' bytes, got '{}'",
+                (self.width, self.height),
+                format,
+                expected,
+                self.bytes.len()
+            ));
+        }
+
+        if self.mips.is_empty() && !desc.mipmaps {
+            return Ok(());
+        }
+
+        if format.is_depth() {
+            return Err("Depth textures cannot have mipmaps".to_string());
+        }
+
+        if desc.mipmaps && !self.mips.is_empty() {
+            return Err(
+                "Cannot generate mipmaps for a texture with explicit mip levels".to_string(),
+            );
+        }
+
+        let can_generate = !format.is_compressed() && format != TextureFormat::Rgba32Float;
+        if desc.mipmaps && !can_generate {
+            return Err(format!(
+                "Cannot generate mipmaps for textures with format '{:?}'",
+                format
+            ));
+        }
+
+        let max_levels = mip_level_count(self.width, self.height);
+        if self.mip_levels(desc) > max_levels {
+            return Err(format!(
+                "Texture with size '{:?}' can have up to '{}' mip levels, got '{}'",
+                (self.width, self.height),
+                max_levels,
+                self.mip_levels(desc)
+            ));
+        }
+
+        self.mips.iter().enumerate().try_for_each(|(i, bytes)| {
+            let level = i as u32 + 1;
+            let (width, height) = mip_level_size(self.width, self.height, level);
+            let expected = format.data_size(width, height);
+            if expected != Some(bytes.len()) {
+                return Err(format!(
+                    "Mip level '{}' with size '{:?}' expects '{}' bytes, got '{}'",
+                    level,
+                    (width, height),
+                    expected.unwrap_or(usize::MAX),
+                    bytes.len()
+                ));
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// Region of a texture in pixels
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct TextureRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
 }
 
 #[derive(Debug, Default, Copy, Clone)]
@@ -66,3 +278,60 @@ pub struct SamplerDescriptor<'a> {
     pub min_filter: TextureFilter,
     pub mipmap_filter: Option<TextureFilter>,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_mip_levels() {
+        assert_eq!(mip_level_count(1, 1), 1);
+        assert_eq!(mip_level_count(256, 64), 9);
+        assert_eq!(mip_level_count(300, 10), 9);
+        assert_eq!(mip_level_size(300, 10, 3), (37, 1));
+        assert_eq!(mip_level_size(300, 10, 8), (1, 1));
+    }
+
+    #[test]
+    fn test_format_data_size() {
+        assert_eq!(TextureFormat::R8Unorm.data_size(3, 2), Some(6));
+        assert_eq!(TextureFormat::Rgba32Float.data_size(3, 2), Some(96));
+        assert_eq!(TextureFormat::Bc1RgbaUnorm.data_size(8, 8), Some(32));
+        // mip levels smaller than a block still use a whole block
+        assert_eq!(TextureFormat::Bc7RgbaUnorm.data_size(2, 1), Some(16));
+        assert_eq!(TextureFormat::Astc4x4Unorm.bytes_per_row(12), Some(48));
+        // sizes that don't fit in the types are not wrapped
+        assert_eq!(TextureFormat::Rgba32Float.bytes_per_row(1 << 28), None);
+        assert_eq!(
+            TextureFormat::Rgba8Unorm.data_size(u32::MAX / 4, 1),
+            Some(u32::MAX as usize - 3)
+        );
+    }
+
+    #[test]
+    fn test_validate_mips() {
+        let level1 = [0; 2 * 4];
+        let level2 = [0; 4];
+        let mips: &[&[u8]] = &[&level1, &level2];
+        let data = TextureData {
+            bytes: &[0; 4 * 2 * 4],
+            width: 4,
+            height: 2,
+            mips,
+        };
+        assert!(data.validate(&TextureDescriptor::default()).is_ok());
+
+        let desc = TextureDescriptor {
+            mipmaps: true,
+            ..Default::default()
+        };
+        assert!(data.validate(&desc).is_err());
+
+        let mips: &[&[u8]] = &[&level2];
+        let data = TextureData { mips, ..data };
+        assert_eq!(
+            data.validate(&TextureDescriptor::default()),
+            Err("Mip level '1' with size '(2, 1)' expects '8' bytes, got '4'".to_string())
+        );
+    }
+}
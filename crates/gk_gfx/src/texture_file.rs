@@ -0,0 +1,350 @@
+use crate::consts::MAX_TEXTURE_SIZE;
+use crate::texture::{mip_level_count, mip_level_size, TextureFormat};
+
+const KTX2_IDENTIFIER: [u8; 12] = [
+    0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+];
+const DDS_MAGIC: &[u8; 4] = b"DDS ";
+
+/// Texture stored in a KTX2 or DDS container, the data is already in the GPU format
+#[derive(Debug)]
+pub(crate) struct TextureFile<'a> {
+    pub format: TextureFormat,
+    pub width: u32,
+    pub height: u32,
+    /// Data of each mip level, starting by the biggest one
+    pub levels: Vec<&'a [u8]>,
+}
+
+impl<'a> TextureFile<'a> {
+    /// Returns `None` if the bytes are not a KTX2 or DDS file
+    pub fn parse(bytes: &'a [u8]) -> Option<Result<Self, String>> {
+        if bytes.starts_with(&KTX2_IDENTIFIER) {
+            Some(parse_ktx2(bytes).map_err(|e| format!("Invalid KTX2 file: {}", e)))
+        } else if bytes.starts_with(DDS_MAGIC) {
+            Some(parse_dds(bytes).map_err(|e| format!("Invalid DDS file: {}", e)))
+        } else {
+            None
+        }
+    }
+}
+
+fn parse_ktx2(bytes: &[u8]) -> Result<TextureFile<'_>, String> {
+    let vk_format = read_u32(bytes, 12)?;
+    let width = read_u32(bytes, 20)?;
+    let height = read_u32(bytes, 24)?;
+    let depth = read_u32(bytes, 28)?;
+    let layers = read_u32(bytes, 32)?;
+    let faces = read_u32(bytes, 36)?;
+    let level_count = read_u32(bytes, 40)?.max(1);
+    let supercompression = read_u32(bytes, 44)?;
+
+    if depth > 1 || layers > 1 || faces > 1 {
+        return Err("Only 2D textures are supported".to_string());
+    }
+
+    validate_size(width, height)?;
+    validate_level_count(width, height, level_count)?;
+
+    if supercompression != 0 {
+        return Err(format!(
+            "Supercompression scheme '{}' is not supported",
+            supercompression
+        ));
+    }
+
+    let format = match vk_format {
+        9 => TextureFormat::R8Unorm,
+        37 => TextureFormat::Rgba8Unorm,
+        43 => TextureFormat::Rgba8UnormSrgb,
+        50 => TextureFormat::Bgra8UnormSrgb,
+        97 => TextureFormat::Rgba16Float,
+        109 => TextureFormat::Rgba32Float,
+        133 => TextureFormat::Bc1RgbaUnorm,
+        134 => TextureFormat::Bc1RgbaUnormSrgb,
+        137 => TextureFormat::Bc3RgbaUnorm,
+        138 => TextureFormat::Bc3RgbaUnormSrgb,
+        139 => TextureFormat::Bc4RUnorm,
+        141 => TextureFormat::Bc5RgUnorm,
+        145 => TextureFormat::Bc7RgbaUnorm,
+        146 => TextureFormat::Bc7RgbaUnormSrgb,
+        147 => TextureFormat::Etc2Rgb8Unorm,
+        148 => TextureFormat::Etc2Rgb8UnormSrgb,
+        151 => TextureFormat::Etc2Rgba8Unorm,
+        152 => TextureFormat::Etc2Rgba8UnormSrgb,
+        157 => TextureFormat::Astc4x4Unorm,
+        158 => TextureFormat::Astc4x4UnormSrgb,
+        _ => return Err(format!("Unsupported VkFormat '{}'", vk_format)),
+    };
+
+    // the level index starts after the header and the data format, key/value and global data indices
+    let levels = (0..level_count)
+        .map(|level| {
+            let index = 80 + level as usize * 24;
+            let offset = read_usize(bytes, index)?;
+            let len = read_usize(bytes, index + 8)?;
+            let (w, h) = mip_level_size(width, height, level);
+            if format.data_size(w, h) != Some(len) {
+                return Err(format!(
+                    "Invalid size '{}' for the mip level '{}'",
+                    len, level
+                ));
+            }
+
+            read_bytes(bytes, offset, len)
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(TextureFile {
+        format,
+        width,
+        height,
+        levels,
+    })
+}
+
+fn parse_dds(bytes: &[u8]) -> Result<TextureFile<'_>, String> {
+    const DDSD_MIPMAPCOUNT: u32 = 0x20000;
+    const DDPF_FOURCC: u32 = 0x4;
+    const DDPF_RGB: u32 = 0x40;
+    const DDSCAPS2_CUBEMAP: u32 = 0x200;
+    const DDSCAPS2_VOLUME: u32 = 0x200000;
+
+    let flags = read_u32(bytes, 8)?;
+    let height = read_u32(bytes, 12)?;
+    let width = read_u32(bytes, 16)?;
+    let level_count = if flags & DDSD_MIPMAPCOUNT != 0 {
+        read_u32(bytes, 28)?.max(1)
+    } else {
+        1
+    };
+    let pf_flags = read_u32(bytes, 80)?;
+    let four_cc = read_bytes(bytes, 84, 4)?;
+    let caps2 = read_u32(bytes, 112)?;
+
+    if caps2 & (DDSCAPS2_CUBEMAP | DDSCAPS2_VOLUME) != 0 {
+        return Err("Only 2D textures are supported".to_string());
+    }
+
+    validate_size(width, height)?;
+    validate_level_count(width, height, level_count)?;
+
+    let (format, data_offset) = if pf_flags & DDPF_FOURCC != 0 {
+        match four_cc {
+            b"DX10" => {
+                let dxgi_format = read_u32(bytes, 128)?;
+                let array_size = read_u32(bytes, 140)?;
+                if array_size > 1 {
+                    return Err("Texture arrays are not supported".to_string());
+                }
+
+                let format = match dxgi_format {
+                    2 => TextureFormat::Rgba32Float,
+                    10 => TextureFormat::Rgba16Float,
+                    28 => TextureFormat::Rgba8Unorm,
+                    29 => TextureFormat::Rgba8UnormSrgb,
+                    61 => TextureFormat::R8Unorm,
+                    71 => TextureFormat::Bc1RgbaUnorm,
+                    72 => TextureFormat::Bc1RgbaUnormSrgb,
+                    77 => TextureFormat::Bc3RgbaUnorm,
+                    78 => TextureFormat::Bc3RgbaUnormSrgb,
+                    80 => TextureFormat::Bc4RUnorm,
+                    83 => TextureFormat::Bc5RgUnorm,
+                    91 => TextureFormat::Bgra8UnormSrgb,
+                    98 => TextureFormat::Bc7RgbaUnorm,
+                    99 => TextureFormat::Bc7RgbaUnormSrgb,
+                    _ => return Err(format!("Unsupported DXGI format '{}'", dxgi_format)),
+                };
+                (format, 148)
+            }
+            b"DXT1" => (TextureFormat::Bc1RgbaUnorm, 128),
+            b"DXT5" => (TextureFormat::Bc3RgbaUnorm, 128),
+            b"ATI1" | b"BC4U" => (TextureFormat::Bc4RUnorm, 128),
+            b"ATI2" | b"BC5U" => (TextureFormat::Bc5RgUnorm, 128),
+            // D3DFMT_A16B16G16R16F and D3DFMT_A32B32G32R32F
+            [113, 0, 0, 0] => (TextureFormat::Rgba16Float, 128),
+            [116, 0, 0, 0] => (TextureFormat::Rgba32Float, 128),
+            _ => {
+                return Err(format!(
+                    "Unsupported FourCC '{}'",
+                    String::from_utf8_lossy(four_cc)
+                ))
+            }
+        }
+    } else {
+        let bit_count = read_u32(bytes, 88)?;
+        let masks = [
+            read_u32(bytes, 92)?,
+            read_u32(bytes, 96)?,
+            read_u32(bytes, 100)?,
+        ];
+        let is_rgba8 =
+            pf_flags & DDPF_RGB != 0 && bit_count == 32 && masks == [0xff, 0xff00, 0xff0000];
+        if !is_rgba8 {
+            return Err("Only RGBA8 uncompressed pixels are supported".to_string());
+        }
+        (TextureFormat::Rgba8Unorm, 128)
+    };
+
+    let mut offset = data_offset;
+    let levels = (0..level_count)
+        .map(|level| {
+            let (w, h) = mip_level_size(width, height, level);
+            let len = format
+                .data_size(w, h)
+                .ok_or_else(|| format!("Invalid size for the mip level '{}'", level))?;
+            let data = read_bytes(bytes, offset, len)?;
+            offset += len;
+            Ok(data)
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(TextureFile {
+        format,
+        width,
+        height,
+        levels,
+    })
+}
+
+fn validate_size(width: u32, height: u32) -> Result<(), String> {
+    let valid = (1..=MAX_TEXTURE_SIZE).contains(&width) && (1..=MAX_TEXTURE_SIZE).contains(&height);
+    if !valid {
+        return Err(format!(
+            "Invalid texture size {}x{}, it must be between 1 and {}",
+            width, height, MAX_TEXTURE_SIZE
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_level_count(width: u32, height: u32, level_count: u32) -> Result<(), String> {
+    let max = mip_level_count(width, height);
+    if level_count > max {
+        return Err(format!(
+            "Invalid mip level count '{}' for a texture of {}x{}, the maximum is '{}'",
+            level_count, width, height, max
+        ));
+    }
+
+    Ok(())
+}
+
+fn read_bytes(bytes: &[u8], offset: usize, len: usize) -> Result<&[u8], String> {
+    offset
+        .checked_add(len)
+        .and_then(|end| bytes.get(offset..end))
+        .ok_or_else(|| "Unexpected end of file".to_string())
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, String> {
+    let data = read_bytes(bytes, offset, 4)?;
+    Ok(u32::from_le_bytes(data.try_into().unwrap()))
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> Result<u64, String> {
+    let data = read_bytes(bytes, offset, 8)?;
+    Ok(u64::from_le_bytes(data.try_into().unwrap()))
+}
+
+fn read_usize(bytes: &[u8], offset: usize) -> Result<usize, String> {
+    let n = read_u64(bytes, offset)?;
+    usize::try_from(n).map_err(|_| format!("Value '{}' is too big for this platform", n))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ktx2(vk_format: u32, width: u32, height: u32, levels: &[&[u8]]) -> Vec<u8> {
+        let mut header = [0u32; 17];
+        header[0] = vk_format;
+        header[2] = width;
+        header[3] = height;
+        header[7] = levels.len() as u32;
+
+        let mut file = KTX2_IDENTIFIER.to_vec();
+        header.iter().for_each(|n| file.extend(n.to_le_bytes()));
+        file.resize(80, 0);
+
+        let mut offset = 80 + levels.len() * 24;
+        levels.iter().for_each(|level| {
+            file.extend((offset as u64).to_le_bytes());
+            file.extend((level.len() as u64).to_le_bytes());
+            file.extend((level.len() as u64).to_le_bytes());
+            offset += level.len();
+        });
+        levels.iter().for_each(|level| file.extend(*level));
+        file
+    }
+
+    #[test]
+    fn test_parse_ktx2() {
+        let level0 = [1; 32];
+        let level1 = [2; 8];
+        let level2 = [3; 8];
+        let bytes = ktx2(133, 8, 8, &[&level0, &level1, &level2]);
+
+        let file = TextureFile::parse(&bytes).unwrap().unwrap();
+        assert_eq!(file.format, TextureFormat::Bc1RgbaUnorm);
+        assert_eq!((file.width, file.height), (8, 8));
+        assert_eq!(file.levels, vec![&level0[..], &level1[..], &level2[..]]);
+
+        let bytes = ktx2(1000, 8, 8, &[&level0]);
+        assert_eq!(
+            TextureFile::parse(&bytes).unwrap().unwrap_err(),
+            "Invalid KTX2 file: Unsupported VkFormat '1000'"
+        );
+
+        assert!(TextureFile::parse(&bytes[..60]).unwrap().is_err());
+
+        // the size comes from the header, it's checked before computing the data size
+        let bytes = ktx2(109, 1 << 28, 1, &[&level0]);
+        assert_eq!(
+            TextureFile::parse(&bytes).unwrap().unwrap_err(),
+            format!(
+                "Invalid KTX2 file: Invalid texture size 268435456x1, it must be between 1 and {}",
+                MAX_TEXTURE_SIZE
+            )
+        );
+        assert!(TextureFile::parse(b"\x89PNG").is_none());
+    }
+
+    #[test]
+    fn test_parse_dds() {
+        let mut bytes = vec![0u8; 128];
+        bytes[..4].copy_from_slice(DDS_MAGIC);
+        bytes[8..12].copy_from_slice(&0x20000u32.to_le_bytes());
+        bytes[12..16].copy_from_slice(&4u32.to_le_bytes());
+        bytes[16..20].copy_from_slice(&8u32.to_le_bytes());
+        bytes[28..32].copy_from_slice(&2u32.to_le_bytes());
+        bytes[80..84].copy_from_slice(&0x4u32.to_le_bytes());
+        bytes[84..88].copy_from_slice(b"DXT5");
+        bytes.extend([1; 32]);
+        bytes.extend([2; 16]);
+
+        let file = TextureFile::parse(&bytes).unwrap().unwrap();
+        assert_eq!(file.format, TextureFormat::Bc3RgbaUnorm);
+        assert_eq!((file.width, file.height), (8, 4));
+        assert_eq!(file.levels, vec![&[1; 32][..], &[2; 16][..]]);
+
+        bytes.truncate(150);
+        assert_eq!(
+            TextureFile::parse(&bytes).unwrap().unwrap_err(),
+            "Invalid DDS file: Unexpected end of file"
+        );
+
+        bytes[28..32].copy_from_slice(&40u32.to_le_bytes());
+        assert_eq!(
+            TextureFile::parse(&bytes).unwrap().unwrap_err(),
+            "Invalid DDS file: Invalid mip level count '40' for a texture of 8x4, the maximum is '4'"
+        );
+
+        bytes[16..20].copy_from_slice(&0u32.to_le_bytes());
+        assert!(TextureFile::parse(&bytes)
+            .unwrap()
+            .unwrap_err()
+            .contains("Invalid texture size 0x4"));
+    }
+}
@@ -0,0 +1,218 @@
+use crate::consts::UNIFORM_BUFFER_OFFSET_ALIGNMENT;
+use crate::{Buffer, Gfx};
+
+/// Uniform buffer split in aligned chunks to use with dynamic offsets.
+/// Each `push` writes the next chunk, call `reset` once per frame to reuse them.
+pub struct UniformRing {
+    buffer: Buffer,
+    chunk_size: u64,
+    stride: u64,
+    capacity: u32,
+    next: u32,
+}
+
+impl UniformRing {
+    pub fn new(gfx: &mut Gfx, chunk_size: u64, capacity: u32) -> Result<Self, String> {
+        if chunk_size == 0 || capacity == 0 {
+            return Err("UniformRing chunk size and capacity must be greater than 0".to_string());
+        }
+
+        let align = UNIFORM_BUFFER_OFFSET_ALIGNMENT as u64;
+        let stride = chunk_size.div_ceil(align) * align;
+        let size = stride * capacity as u64;
+        if size > u32::MAX as u64 {
+            return Err(format!("UniformRing size '{}' is too big", size));
+        }
+
+        let buffer = gfx
+            .create_uniform_buffer(&vec![0u8; size as usize])
+            .with_label("UniformRing")
+            .with_write_flag(true)
+            .build()?;
+
+        Ok(Self {
+            buffer,
+            chunk_size,
+            stride,
+            capacity,
+            next: 0,
+        })
+    }
+
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Size to use with `BindGroupBuilder::with_dynamic_uniform`
+    pub fn chunk_size(&self) -> u64 {
+        self.chunk_size
+    }
+
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Chunks used since the last reset
+    pub fn len(&self) -> u32 {
+        self.next
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.next == 0
+    }
+
+    /// Writes the data in the next chunk and returns its dynamic offset
+    pub fn push<D: bytemuck::Pod>(&mut self, gfx: &mut Gfx, data: &[D]) -> Result<u32, String> {
+        let bytes: &[u8] = bytemuck::cast_slice(data);
+        if bytes.len() as u64 > self.chunk_size {
+            return Err(format!(
+                "Cannot push '{}' bytes in a UniformRing with chunks of '{}' bytes",
+                bytes.len(),
+                self.chunk_size
+            ));
+        }
+
+        if self.next >= self.capacity {
+            return Err(format!(
+                "UniformRing is full, all the '{}' chunks are used",
+                self.capacity
+            ));
+        }
+
+        let offset = self.next as u64 * self.stride;
+        gfx.write_buffer(&self.buffer)
+            .with_offset(offset)
+            .with_data(bytes)
+            .build()?;
+        self.next += 1;
+
+        Ok(offset as _)
+    }
+
+    /// Chunks pushed before are overwritten by the next pushes
+    pub fn reset(&mut self) {
+        self.next = 0;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_utils::headless_gfx;
+    use crate::*;
+
+    #[test]
+    fn test_uniform_ring() {
+        let mut gfx = headless_gfx();
+
+        let mut ring = UniformRing::new(&mut gfx, 64, 2).unwrap();
+        assert_eq!(ring.buffer().len(), 512);
+        assert_eq!(ring.push(&mut gfx, &[1.0f32; 16]).unwrap(), 0);
+        assert_eq!(ring.push(&mut gfx, &[2.0f32; 4]).unwrap(), 256);
+        assert!(ring.push(&mut gfx, &[3.0f32; 4]).is_err());
+
+        ring.reset();
+        assert!(ring.is_empty());
+        assert!(ring.push(&mut gfx, &[0.0f32; 17]).is_err());
+
+        let layout = BindGroupLayout::new().with_entry(
+            BindingType::uniform(0)
+                .with_vertex_visibility(true)
+                .with_dynamic_offset(true),
+        );
+        let pip = gfx
+            .create_render_pipeline("")
+            .with_bind_group_layout(layout)
+            .build()
+            .unwrap();
+        let bind_group = gfx
+            .create_bind_group()
+            .with_layout(pip.bind_group_layout_id(0).unwrap())
+            .with_dynamic_uniform(0, ring.buffer(), ring.chunk_size())
+            .build()
+            .unwrap();
+        assert!(gfx
+            .create_bind_group()
+            .with_layout(pip.bind_group_layout_id(0).unwrap())
+            .with_dynamic_uniform(0, ring.buffer(), 1024)
+            .build()
+            .is_err());
+
+        let rt = gfx
+            .create_render_texture()
+            .with_size(10, 10)
+            .build()
+            .unwrap();
+        let mut renderer = Renderer::new();
+        renderer
+            .begin_pass()
+            .pipeline(&pip)
+            .bindings(&[&bind_group])
+            .dynamic_offsets(0, &[0])
+            .draw(0..3)
+            .dynamic_offsets(0, &[256])
+            .draw(0..3);
+        gfx.render(&rt, &renderer).unwrap();
+
+        let draws = &gfx.recorded_passes()[0].draws;
+        assert_eq!(draws[0].dynamic_offsets, vec![vec![0]]);
+        assert_eq!(draws[1].dynamic_offsets, vec![vec![256]]);
+
+        let mut renderer = Renderer::new();
+        renderer
+            .begin_compute_pass()
+            .bindings(&[&bind_group])
+            .dispatch(1, 1, 1);
+        assert_eq!(
+            gfx.compute(&renderer).unwrap_err(),
+            "The bind group '0' uses dynamic offsets, they are not supported on compute passes"
+        );
+
+        let mut renderer = Renderer::new();
+        renderer
+            .begin_pass()
+            .bindings(&[&bind_group])
+            .dynamic_offsets(0, &[64])
+            .draw(0..3);
+        assert!(gfx.render(&rt, &renderer).is_err());
+
+        let mut renderer = Renderer::new();
+        renderer
+            .begin_pass()
+            .bindings(&[&bind_group])
+            .dynamic_offsets(4, &[0; 9])
+            .draw(0..3);
+        assert_eq!(
+            gfx.render(&rt, &renderer).unwrap_err(),
+            "Dynamic offsets set for the bind group '4' but only '1' are bound"
+        );
+
+        let mut renderer = Renderer::new();
+        renderer.begin_pass().bindings(&[&bind_group]).draw(0..3);
+        assert_eq!(
+            gfx.render(&rt, &renderer).unwrap_err(),
+            "The bind group '0' needs 1 dynamic offsets but none were set"
+        );
+
+        let mut renderer = Renderer::new();
+        renderer
+            .begin_pass()
+            .bindings(&[&bind_group])
+            .dynamic_offsets(0, &[0, 256])
+            .draw(0..3);
+        assert_eq!(
+            gfx.render(&rt, &renderer).unwrap_err(),
+            "The bind group '0' needs 1 dynamic offsets but 2 were set"
+        );
+
+        let mut renderer = Renderer::new();
+        renderer
+            .begin_pass()
+            .bindings(&[&bind_group])
+            .dynamic_offsets(0, &[512])
+            .draw(0..3);
+        assert_eq!(
+            gfx.render(&rt, &renderer).unwrap_err(),
+            "Dynamic offset '512' with a binding of '64' bytes is out of the buffer of '512' bytes"
+        );
+    }
+}
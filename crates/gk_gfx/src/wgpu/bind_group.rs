@@ -1,3 +1,4 @@
+use crate::bind_group::DynamicBinding;
 use crate::{BindGroupId, BindGroupLayoutId, GKBindGroup, GKBindGroupLayoutRef};
 use std::sync::Arc;
 use wgpu::{BindGroup as RawBindGroup, BindGroupLayout};
@@ -5,6 +6,7 @@ use wgpu::{BindGroup as RawBindGroup, BindGroupLayout};
 #[derive(Clone)]
 pub struct BindGroup {
     pub(crate) id: BindGroupId,
+    pub(crate) dynamic_bindings: Arc<[DynamicBinding]>,
     pub(crate) raw: Arc<RawBindGroup>,
 }
 
@@ -17,6 +19,7 @@ impl GKBindGroup for BindGroup {
 #[derive(Clone)]
 pub struct BindGroupLayoutRef {
     pub(crate) id: BindGroupLayoutId,
+    pub(crate) dynamic_uniforms: Arc<[u32]>,
     pub(crate) raw: Arc<BindGroupLayout>,
 }
 
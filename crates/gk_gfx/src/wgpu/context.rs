@@ -10,10 +10,10 @@ pub(crate) struct Context {
 }
 
 impl Context {
-    pub fn new(_attrs: GfxAttributes) -> Result<Self, String> {
-        // TODO why attrs is required here?
+    pub fn new(attrs: GfxAttributes) -> Result<Self, String> {
         let instance = Instance::default();
-        let (adapter, device, queue) = pollster::block_on(generate_inner(&instance, None))?;
+        let (adapter, device, queue) =
+            pollster::block_on(generate_inner(&instance, None, attrs.headless))?;
 
         println!("{:?}", adapter.get_info()); // TODO check if d3d11 to apply limits?
 
@@ -31,7 +31,7 @@ impl Context {
 
     pub fn ensure_surface_compatibility(&mut self, surface: &RawSurface) -> Result<(), String> {
         let (adapter, device, queue) =
-            pollster::block_on(generate_inner(&self.instance, Some(surface)))?;
+            pollster::block_on(generate_inner(&self.instance, Some(surface), false))?;
         self.adapter = adapter;
         self.device = device;
         self.queue = queue;
@@ -42,15 +42,29 @@ impl Context {
 async fn generate_inner(
     instance: &Instance,
     _surface: Option<&RawSurface>, // TODO why this is here?
+    allow_fallback: bool,
 ) -> Result<(Adapter, Device, Queue), String> {
-    let adapter = instance
+    let mut adapter = instance
         .request_adapter(&wgpu::RequestAdapterOptions {
             power_preference: PowerPreference::HighPerformance,
             force_fallback_adapter: false,
             compatible_surface: None,
         })
-        .await
-        .ok_or_else(|| "Cannot create WGPU Adapter for {:?}".to_string())?;
+        .await;
+
+    // machines without a GPU (like CI) can still use the software adapter (llvmpipe, warp...)
+    if adapter.is_none() && allow_fallback {
+        log::warn!("No hardware adapter found, using the fallback (software) adapter.");
+        adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: PowerPreference::HighPerformance,
+                force_fallback_adapter: true,
+                compatible_surface: None,
+            })
+            .await;
+    }
+
+    let adapter = adapter.ok_or_else(|| "Cannot create WGPU Adapter".to_string())?;
 
     // TODO depending on adapter here, require limits for it.
     let limits = if cfg!(all(target_arch = "wasm32", feature = "webgl")) {
@@ -59,11 +73,17 @@ async fn generate_inner(
         wgpu::Limits::default()
     };
 
+    // compressed textures can be used only if the adapter supports them
+    let compression = wgpu::Features::TEXTURE_COMPRESSION_BC
+        | wgpu::Features::TEXTURE_COMPRESSION_ETC2
+        | wgpu::Features::TEXTURE_COMPRESSION_ASTC;
+    let features = adapter.features() & compression;
+
     let (device, queue) = adapter
         .request_device(
             &wgpu::DeviceDescriptor {
                 label: None,
-                features: wgpu::Features::default(),
+                features,
                 limits,
             },
             None,
@@ -1,26 +1,32 @@
 use super::buffer::Buffer;
 use super::context::Context;
-use super::pipeline::RenderPipeline;
+use super::mipmaps::MipmapGenerator;
+use super::pipeline::{ComputePipeline, RenderPipeline, RenderPipelineSource, VertexBufferSource};
 use super::surface::Surface;
-use super::texture::Texture;
+use super::texture::{MsaaTexture, Texture};
 use super::utils::wgpu_color;
 use crate::attrs::GfxAttributes;
 use crate::bind_group::BindType;
 use crate::buffer::{BufferDescriptor, BufferUsage};
+use crate::consts::{
+    DEFAULT_COMPUTE_ENTRY, DEFAULT_FRAGMENT_ENTRY, DEFAULT_VERTEX_ENTRY,
+    MAX_BIND_GROUPS_PER_PIPELINE,
+};
 use crate::device::GKDevice;
-use crate::pipeline::RenderPipelineDescriptor;
+use crate::pipeline::{ComputePipelineDescriptor, RenderPipelineDescriptor};
 use crate::render_texture::RenderTextureDescriptor;
-use crate::renderer::Renderer;
-use crate::texture::TextureDescriptor;
+use crate::renderer::{ComputePass, Pass, RPassVertices, RenderPass, Renderer, Viewport};
+use crate::texture::{mip_level_size, TextureDescriptor};
 use crate::wgpu::render_texture::RenderTexture;
 use crate::wgpu::utils::{
-    wgpu_blend_mode, wgpu_buffer_usages, wgpu_cull_mode, wgpu_depth_stencil, wgpu_index_format,
-    wgpu_primitive, wgpu_shader_visibility, wgpu_step_mode, wgpu_texture_filter,
-    wgpu_texture_format, wgpu_texture_wrap, wgpu_vertex_format, wgpu_write_mask,
+    padded_bytes_per_row, remove_row_padding, wgpu_blend_mode, wgpu_buffer_usages, wgpu_cull_mode,
+    wgpu_depth_stencil, wgpu_index_format, wgpu_primitive, wgpu_shader_visibility, wgpu_step_mode,
+    wgpu_texture_filter, wgpu_texture_format, wgpu_texture_wrap, wgpu_vertex_format,
+    wgpu_write_mask,
 };
 use crate::{
-    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayoutId, BindGroupLayoutRef,
-    DrawFrame, GKBuffer, Sampler, SamplerDescriptor, TextureData, TextureFormat, TextureId,
+    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutRef, DrawFrame,
+    GKBuffer, Sampler, SamplerDescriptor, TextureData, TextureFormat, TextureId, TextureRect,
     MAX_BINDING_ENTRIES,
 };
 use arrayvec::ArrayVec;
@@ -38,11 +44,26 @@ pub struct Device {
     attrs: GfxAttributes,
     ctx: Context,
     depth_format: TextureFormat,
+    mipmaps: Option<MipmapGenerator>,
     pub(crate) surfaces: HashMap<WindowId, Surface>,
 }
 
 impl Plugin for Device {}
 
+impl Device {
+    /// Generate the mip chain from the first level if the texture uses mipmaps
+    fn generate_mipmaps(&mut self, texture: &Texture) {
+        if !texture.mipmaps || texture.mip_levels <= 1 {
+            return;
+        }
+
+        let device = &self.ctx.device;
+        self.mipmaps
+            .get_or_insert_with(|| MipmapGenerator::new(device))
+            .generate(device, &self.ctx.queue, &texture.raw, texture.mip_levels);
+    }
+}
+
 impl
     GKDevice<
         DrawFrame,
@@ -53,6 +74,7 @@ impl
         BindGroup,
         BindGroupLayoutRef,
         RenderTexture,
+        ComputePipeline,
     > for Device
 {
     fn new(attrs: GfxAttributes) -> Result<Self, String> {
@@ -62,6 +84,7 @@ impl
             attrs,
             ctx: context,
             depth_format: attrs.depth_format,
+            mipmaps: None,
             surfaces: HashMap::default(),
         })
     }
@@ -112,24 +135,36 @@ impl
     }
 
     fn init_surface<W: GKWindow>(&mut self, window: &W) -> Result<(), String> {
+        if self.attrs.headless {
+            return Err("Cannot initialize a surface on a headless device".to_string());
+        }
+
         if self.surfaces.contains_key(&window.id()) {
             return Ok(());
         }
 
-        let depth_texture = self.create_texture(
+        let depth_texture = create_texture(
+            &self.ctx.device,
+            &self.ctx.queue,
             TextureDescriptor {
                 label: Some("Depth Texture for Surface"),
                 format: self.depth_format,
                 write: true,
+                storage: false,
+                mipmaps: false,
             },
             Some(TextureData {
                 bytes: &[],
                 width: window.width(),
                 height: window.height(),
+                mips: &[],
             }),
+            self.attrs.msaa_samples,
+            resource_id(&mut self.next_resource_id),
         )?;
 
-        let surface = Surface::new(&mut self.ctx, window, self.attrs, depth_texture)?;
+        let mut surface = Surface::new(&mut self.ctx, window, self.attrs, depth_texture)?;
+        surface.update_msaa(&self.ctx.device, self.attrs.msaa_samples);
         self.surfaces.insert(window.id(), surface);
 
         Ok(())
@@ -139,6 +174,13 @@ impl
         &mut self,
         desc: RenderPipelineDescriptor,
     ) -> Result<RenderPipeline, String> {
+        if desc.stencil.is_some() && !self.depth_format.has_stencil() {
+            return Err(format!(
+                "Stencil needs a depth format with stencil, got '{:?}'",
+                self.depth_format
+            ));
+        }
+
         let shader = self
             .ctx
             .device
@@ -147,61 +189,8 @@ impl
                 source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(desc.shader)),
             });
 
-        let mut bind_group_layouts = desc
-            .bind_group_layout
-            .iter()
-            .map(|bgl| {
-                self.ctx
-                    .device
-                    .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                        label: desc.label,
-                        entries: &bgl
-                            .entries
-                            .iter()
-                            .map(|entry| {
-                                let visibility = wgpu_shader_visibility(
-                                    entry.visible_vertex,
-                                    entry.visible_fragment,
-                                    entry.visible_compute,
-                                );
-                                let binding = entry.location;
-                                match entry.typ {
-                                    BindType::Texture => wgpu::BindGroupLayoutEntry {
-                                        binding,
-                                        visibility,
-                                        ty: wgpu::BindingType::Texture {
-                                            multisampled: false,
-                                            view_dimension: wgpu::TextureViewDimension::D2,
-                                            sample_type: wgpu::TextureSampleType::Float {
-                                                filterable: true,
-                                            },
-                                        },
-                                        count: None,
-                                    },
-                                    BindType::Sampler => wgpu::BindGroupLayoutEntry {
-                                        binding,
-                                        visibility,
-                                        ty: wgpu::BindingType::Sampler(
-                                            wgpu::SamplerBindingType::Filtering,
-                                        ),
-                                        count: None,
-                                    },
-                                    BindType::Uniform => wgpu::BindGroupLayoutEntry {
-                                        binding,
-                                        visibility,
-                                        ty: wgpu::BindingType::Buffer {
-                                            ty: wgpu::BufferBindingType::Uniform,
-                                            has_dynamic_offset: false,
-                                            min_binding_size: None,
-                                        },
-                                        count: None,
-                                    },
-                                }
-                            })
-                            .collect::<Vec<_>>(),
-                    })
-            })
-            .collect::<Vec<_>>();
+        let bind_group_layouts =
+            create_bind_group_layouts(&self.ctx.device, desc.label, &desc.bind_group_layout);
 
         let pipeline_layout =
             self.ctx
@@ -213,20 +202,21 @@ impl
                 });
 
         // TODO is this right? I don't see issues if we keep formats the same for now
+        // without surfaces (headless) use the same format as RenderTextures
         let swapchain_format = self
             .surfaces
             .iter()
             .next() // get first surface
-            .map_or(wgpu::TextureFormat::Rgba8UnormSrgb, |(_, surface)| {
+            .map_or(wgpu::TextureFormat::Bgra8UnormSrgb, |(_, surface)| {
                 surface.capabilities.formats[0]
             });
 
-        let (attrs, mut buffers): (Vec<_>, Vec<_>) = desc
+        let buffers = desc
             .vertex_layout
             .iter()
             .map(|vl| {
                 let mut offset = 0;
-                let attrs = vl
+                let attributes = vl
                     .attributes
                     .iter()
                     .map(|attr| {
@@ -240,20 +230,13 @@ impl
                     })
                     .collect::<Vec<_>>();
 
-                let layout = wgpu::VertexBufferLayout {
+                VertexBufferSource {
                     array_stride: offset,
                     step_mode: wgpu_step_mode(vl.step_mode),
-                    attributes: &[],
-                };
-
-                (attrs, layout)
+                    attributes,
+                }
             })
-            .unzip();
-
-        buffers
-            .iter_mut()
-            .enumerate()
-            .for_each(|(i, buff)| buff.attributes = &attrs[i]);
+            .collect();
 
         let swapchain_color_target: wgpu::ColorTargetState = swapchain_format.into();
         let color_target = wgpu::ColorTargetState {
@@ -262,47 +245,85 @@ impl
             ..swapchain_color_target
         };
 
+        let source = RenderPipelineSource {
+            label: desc.label.map(str::to_string),
+            shader,
+            layout: pipeline_layout,
+            vs_entry: desc.vs_entry.unwrap_or(DEFAULT_VERTEX_ENTRY).to_string(),
+            fs_entry: desc.fs_entry.unwrap_or(DEFAULT_FRAGMENT_ENTRY).to_string(),
+            buffers,
+            color_target,
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu_primitive(desc.primitive),
+                cull_mode: desc.cull_mode.map(wgpu_cull_mode),
+                ..Default::default()
+            },
+            depth_stencil: wgpu_depth_stencil(self.depth_format, desc.depth_stencil, desc.stencil),
+            pipelines: Default::default(),
+        };
+
+        let index_format = wgpu_index_format(desc.index_format);
+        let bind_group_layout = bind_group_layout_refs(
+            &mut self.next_resource_id,
+            &desc.bind_group_layout,
+            bind_group_layouts,
+        );
+        let pipeline = RenderPipeline {
+            id: resource_id(&mut self.next_resource_id),
+            source: Arc::new(source),
+            index_format,
+            uses_depth: desc.depth_stencil.is_some(),
+            uses_stencil: desc.stencil.is_some(),
+            bind_group_layout,
+        };
+
+        // the surfaces' variant is created now, other sample counts when they are used
+        pipeline.raw(&self.ctx.device, self.attrs.msaa_samples);
+        Ok(pipeline)
+    }
+
+    fn create_compute_pipeline(
+        &mut self,
+        desc: ComputePipelineDescriptor,
+    ) -> Result<ComputePipeline, String> {
+        let shader = self
+            .ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: desc.label,
+                source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(desc.shader)),
+            });
+
+        let bind_group_layouts =
+            create_bind_group_layouts(&self.ctx.device, desc.label, &desc.bind_group_layout);
+
+        let pipeline_layout =
+            self.ctx
+                .device
+                .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                    label: desc.label,
+                    bind_group_layouts: &bind_group_layouts.iter().collect::<Vec<&_>>(),
+                    push_constant_ranges: &[],
+                });
+
         let raw = self
             .ctx
             .device
-            .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
                 label: desc.label,
                 layout: Some(&pipeline_layout),
-                vertex: wgpu::VertexState {
-                    module: &shader,
-                    entry_point: desc.vs_entry.unwrap_or("vs_main"),
-                    buffers: &buffers,
-                },
-                fragment: Some(wgpu::FragmentState {
-                    module: &shader,
-                    entry_point: desc.fs_entry.unwrap_or("fs_main"),
-                    targets: &[Some(color_target)],
-                }),
-                primitive: wgpu::PrimitiveState {
-                    topology: wgpu_primitive(desc.primitive),
-                    cull_mode: desc.cull_mode.map(wgpu_cull_mode),
-                    ..Default::default()
-                },
-                depth_stencil: wgpu_depth_stencil(desc.depth_stencil, desc.stencil),
-                multisample: wgpu::MultisampleState::default(),
-                multiview: None,
+                module: &shader,
+                entry_point: desc.entry.unwrap_or(DEFAULT_COMPUTE_ENTRY),
             });
 
-        let index_format = wgpu_index_format(desc.index_format);
-        let mut bind_group_layout = ArrayVec::new();
-        bind_group_layouts.reverse();
-        while let Some(bgl) = bind_group_layouts.pop() {
-            bind_group_layout.push(BindGroupLayoutRef {
-                id: resource_id(&mut self.next_resource_id),
-                raw: Arc::new(bgl),
-            });
-        }
-        Ok(RenderPipeline {
+        let bind_group_layout = bind_group_layout_refs(
+            &mut self.next_resource_id,
+            &desc.bind_group_layout,
+            bind_group_layouts,
+        );
+        Ok(ComputePipeline {
             id: resource_id(&mut self.next_resource_id),
             raw: Arc::new(raw),
-            index_format,
-            uses_depth: desc.depth_stencil.is_some(),
-            uses_stencil: desc.stencil.is_some(),
             bind_group_layout,
         })
     }
@@ -342,28 +363,39 @@ impl
                 // TODO allow more formats? this leads to panics dealing with pipeline vs texture vs surface formats
                 format: TextureFormat::Bgra8UnormSrgb,
                 write: true,
+                storage: false,
+                mipmaps: false,
             },
             Some(TextureData {
                 bytes: &[],
                 width: desc.width,
                 height: desc.height,
+                mips: &[],
             }),
         )?;
 
-        // Create the depth texture
+        // Create the depth texture, it must use the same samples than the color attachment
+        let samples = desc.samples.max(1);
         let depth_texture = {
             let tex = desc.depth.then(|| {
-                self.create_texture(
+                create_texture(
+                    &self.ctx.device,
+                    &self.ctx.queue,
                     TextureDescriptor {
                         label: Some("Create RenderTexture inner color texture"),
-                        format: TextureFormat::Depth32Float,
+                        format: self.depth_format,
                         write: true,
+                        storage: false,
+                        mipmaps: false,
                     },
                     Some(TextureData {
                         bytes: &[],
                         width: desc.width,
                         height: desc.height,
+                        mips: &[],
                     }),
+                    samples,
+                    resource_id(&mut self.next_resource_id),
                 )
             });
 
@@ -374,26 +406,49 @@ impl
             }
         };
 
+        let msaa_texture = (samples > 1).then(|| {
+            Arc::new(MsaaTexture::new(
+                &self.ctx.device,
+                wgpu_texture_format(texture.format),
+                texture.size,
+                samples,
+            ))
+        });
+
         Ok(RenderTexture {
             id: resource_id(&mut self.next_resource_id),
             texture,
             depth_texture,
+            msaa_texture,
+            samples,
         })
     }
 
+    fn is_format_supported(&self, format: TextureFormat) -> bool {
+        let required = wgpu_texture_format(format).required_features();
+        self.ctx.device.features().contains(required)
+    }
+
     fn create_texture(
         &mut self,
         desc: TextureDescriptor,
         data: Option<TextureData>,
     ) -> Result<Texture, String> {
         let id = resource_id(&mut self.next_resource_id);
-        create_texture(&self.ctx.device, &self.ctx.queue, desc, data, id)
+        let texture = create_texture(&self.ctx.device, &self.ctx.queue, desc, data, 1, id)?;
+
+        let has_data = data.is_some_and(|d| !d.bytes.is_empty());
+        if has_data {
+            self.generate_mipmaps(&texture);
+        }
+
+        Ok(texture)
     }
 
     fn write_buffer(&mut self, buffer: &Buffer, offset: u64, data: &[u8]) -> Result<(), String> {
         debug_assert!(buffer.write, "Cannot write data to a static buffer");
         debug_assert!(
-            buffer.len() <= offset as usize + data.len(),
+            offset as usize + data.len() <= buffer.len(),
             "Invalid buffer size '{}' expected '{}'",
             buffer.len(),
             offset as usize + data.len()
@@ -402,6 +457,22 @@ impl
         Ok(())
     }
 
+    fn write_texture(
+        &mut self,
+        texture: &Texture,
+        rect: TextureRect,
+        data: &[u8],
+    ) -> Result<(), String> {
+        debug_assert!(texture.write, "Cannot write data to a static texture");
+        if rect.width == 0 || rect.height == 0 {
+            return Ok(());
+        }
+
+        write_texture_data(&self.ctx.queue, &texture.raw, texture.format, 0, rect, data);
+        self.generate_mipmaps(texture);
+        Ok(())
+    }
+
     fn create_sampler(&mut self, desc: SamplerDescriptor) -> Result<Sampler, String> {
         let raw = self.ctx.device.create_sampler(&wgpu::SamplerDescriptor {
             label: desc.label,
@@ -422,6 +493,7 @@ impl
     }
 
     fn create_bind_group(&mut self, desc: BindGroupDescriptor) -> Result<BindGroup, String> {
+        let dynamic_bindings = desc.dynamic_bindings()?;
         let mut entries: ArrayVec<_, MAX_BINDING_ENTRIES> = Default::default();
         desc.entry.iter().for_each(|entry| match entry {
             BindGroupEntry::Texture { location, texture } => {
@@ -430,10 +502,18 @@ impl
                     resource: wgpu::BindingResource::TextureView(&texture.view),
                 });
             }
-            BindGroupEntry::Uniform { location, buffer } => {
+            BindGroupEntry::Uniform {
+                location,
+                buffer,
+                size,
+            } => {
                 entries.push(wgpu::BindGroupEntry {
                     binding: *location,
-                    resource: buffer.raw.as_entire_binding(),
+                    resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                        buffer: &buffer.raw,
+                        offset: 0,
+                        size: size.and_then(wgpu::BufferSize::new),
+                    }),
                 });
             }
             BindGroupEntry::Sampler { location, sampler } => {
@@ -442,6 +522,18 @@ impl
                     resource: wgpu::BindingResource::Sampler(&sampler.raw),
                 });
             }
+            BindGroupEntry::StorageBuffer { location, buffer } => {
+                entries.push(wgpu::BindGroupEntry {
+                    binding: *location,
+                    resource: buffer.raw.as_entire_binding(),
+                });
+            }
+            BindGroupEntry::StorageTexture { location, texture } => {
+                entries.push(wgpu::BindGroupEntry {
+                    binding: *location,
+                    resource: wgpu::BindingResource::TextureView(&texture.view),
+                });
+            }
         });
         let raw = self
             .ctx
@@ -457,10 +549,78 @@ impl
 
         Ok(BindGroup {
             id: resource_id(&mut self.next_resource_id),
+            dynamic_bindings: dynamic_bindings.into(),
             raw: Arc::new(raw),
         })
     }
 
+    fn read_pixels(&mut self, texture: &Texture, rect: TextureRect) -> Result<Vec<u8>, String> {
+        if texture.format.is_depth() {
+            return Err("Cannot read pixels from a depth texture".to_string());
+        }
+
+        if texture.format.is_compressed() || texture.format.block_bytes() != 4 {
+            return Err(format!(
+                "Cannot read pixels from a texture with format '{:?}'",
+                texture.format
+            ));
+        }
+
+        let encoder = self
+            .ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Read Pixels Encoder"),
+            });
+
+        read_texture_pixels(
+            &self.ctx,
+            encoder,
+            &texture.raw,
+            wgpu_texture_format(texture.format),
+            texture.size,
+            rect,
+        )
+    }
+
+    fn read_frame_pixels(
+        &mut self,
+        frame: &DrawFrame,
+        rect: TextureRect,
+    ) -> Result<Vec<u8>, String> {
+        let can_copy = frame
+            .surface
+            .config
+            .usage
+            .contains(wgpu::TextureUsages::COPY_SRC);
+        if !can_copy {
+            return Err(format!(
+                "The surface for {:?} doesn't allow to read pixels",
+                frame.window_id
+            ));
+        }
+
+        // The work done until now needs to be submitted before copy the pixels,
+        // so we swap the frame's encoder with a new one to keep rendering on it
+        let encoder = self
+            .ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Frame Encode"),
+            });
+        let encoder = frame.encoder.replace(encoder);
+
+        let size = (frame.surface.config.width, frame.surface.config.height);
+        read_texture_pixels(
+            &self.ctx,
+            encoder,
+            &frame.frame.texture,
+            frame.surface.config.format,
+            size,
+            rect,
+        )
+    }
+
     fn resize(&mut self, id: WindowId, width: u32, height: u32) -> Result<(), String> {
         if let Some(surface) = self.surfaces.get_mut(&id) {
             surface.resize(&self.ctx.device, width, height);
@@ -472,9 +632,11 @@ impl
                 &self.ctx.queue,
                 surface,
                 self.depth_format,
+                self.attrs.msaa_samples,
                 Some("Resize surface's depth texture"),
                 id,
             )?;
+            surface.update_msaa(&self.ctx.device, self.attrs.msaa_samples);
         }
 
         Ok(())
@@ -491,23 +653,39 @@ impl
         renderer
             .passes
             .iter()
-            .try_for_each(|rp| -> Result<(), String> {
+            .try_for_each(|pass| -> Result<(), String> {
+                let rp = match pass {
+                    Pass::Render(rp) => rp,
+                    Pass::Compute(cp) => {
+                        encode_compute_pass(&mut frame.encoder.borrow_mut(), cp);
+                        return Ok(());
+                    }
+                };
+
                 let (uses_depth, uses_stencil) = rp
                     .pipeline
                     .map_or((false, false), |pip| (pip.uses_depth, pip.uses_stencil));
 
+                let (view, resolve_target) = match &frame.surface.msaa_texture {
+                    Some(msaa) => (&msaa.view, Some(&frame.view)),
+                    None => (&frame.view, None),
+                };
+                let samples = frame.surface.msaa_texture.as_ref().map_or(1, |m| m.samples);
+                let raw_pipeline = rp.pipeline.map(|pip| pip.raw(&self.ctx.device, samples));
+                let target_size = (frame.surface.config.width, frame.surface.config.height);
+
                 let color = Some(rp.clear_options.color.map_or_else(
                     || wgpu::RenderPassColorAttachment {
-                        view: &frame.view,
-                        resolve_target: None,
+                        view,
+                        resolve_target,
                         ops: wgpu::Operations {
                             load: wgpu::LoadOp::Load,
                             store: true,
                         },
                     },
                     |color| wgpu::RenderPassColorAttachment {
-                        view: &frame.view,
-                        resolve_target: None,
+                        view,
+                        resolve_target,
                         ops: wgpu::Operations {
                             load: rp.clear_options.color.map_or(wgpu::LoadOp::Load, |color| {
                                 wgpu::LoadOp::Clear(wgpu_color(color))
@@ -556,37 +734,41 @@ impl
                     },
                 });
 
-                if let Some(pip) = rp.pipeline {
-                    rpass.set_pipeline(&pip.raw);
+                if let (Some(pip), Some(raw)) = (rp.pipeline, &raw_pipeline) {
+                    rpass.set_pipeline(raw);
 
                     let mut vertex_buffers_slot = 0;
                     let mut indexed = false;
-                    rp.buffers.iter().for_each(|buff| match buff.usage {
-                        BufferUsage::Vertex => {
-                            rpass.set_vertex_buffer(vertex_buffers_slot, buff.raw.slice(..));
+                    rp.buffers.iter().for_each(|rb| match rb.buffer.usage {
+                        BufferUsage::Vertex | BufferUsage::Storage => {
+                            let slice = rb.buffer.raw.slice(rb.range());
+                            rpass.set_vertex_buffer(vertex_buffers_slot, slice);
                             vertex_buffers_slot += 1;
                         }
                         BufferUsage::Index => {
                             debug_assert!(!indexed, "Cannot bind more than one Index buffer");
                             indexed = true;
-                            rpass.set_index_buffer(buff.raw.slice(..), pip.index_format)
+                            let slice = rb.buffer.raw.slice(rb.range());
+                            rpass.set_index_buffer(slice, pip.index_format)
                         }
                         BufferUsage::Uniform => {}
                     });
 
-                    rp.bind_groups.iter().enumerate().for_each(|(i, bg)| {
-                        rpass.set_bind_group(i as _, &bg.raw, &[]);
-                    });
-
                     if let Some(sr) = rp.stencil_ref {
                         rpass.set_stencil_reference(sr as _);
                     }
 
-                    rp.vertices.iter().for_each(|vertices| {
+                    rp.vertices.iter().enumerate().for_each(|(i, vertices)| {
+                        set_bind_groups(&mut rpass, rp, i);
+                        set_draw_area(&mut rpass, vertices, target_size);
                         if !vertices.range.is_empty() {
                             let instances = 0..vertices.instances.unwrap_or(1);
                             if indexed {
-                                rpass.draw_indexed(vertices.range.clone(), 0, instances);
+                                rpass.draw_indexed(
+                                    vertices.range.clone(),
+                                    vertices.base_vertex,
+                                    instances,
+                                );
                             } else {
                                 rpass.draw(vertices.range.clone(), instances);
                             }
@@ -624,23 +806,40 @@ impl
         renderer
             .passes
             .iter()
-            .try_for_each(|rp| -> Result<(), String> {
+            .try_for_each(|pass| -> Result<(), String> {
+                let rp = match pass {
+                    Pass::Render(rp) => rp,
+                    Pass::Compute(cp) => {
+                        encode_compute_pass(&mut encoder, cp);
+                        return Ok(());
+                    }
+                };
+
                 let (uses_depth, uses_stencil) = rp
                     .pipeline
                     .map_or((false, false), |pip| (pip.uses_depth, pip.uses_stencil));
 
+                let (view, resolve_target) = match &frame.msaa_texture {
+                    Some(msaa) => (&msaa.view, Some(&*frame.texture.view)),
+                    None => (&*frame.texture.view, None),
+                };
+                let raw_pipeline = rp
+                    .pipeline
+                    .map(|pip| pip.raw(&self.ctx.device, frame.samples));
+                let target_size = frame.texture.size;
+
                 let color = Some(rp.clear_options.color.map_or_else(
                     || wgpu::RenderPassColorAttachment {
-                        view: &frame.texture.view,
-                        resolve_target: None,
+                        view,
+                        resolve_target,
                         ops: wgpu::Operations {
                             load: wgpu::LoadOp::Load,
                             store: true,
                         },
                     },
                     |color| wgpu::RenderPassColorAttachment {
-                        view: &frame.texture.view,
-                        resolve_target: None,
+                        view,
+                        resolve_target,
                         ops: wgpu::Operations {
                             load: rp.clear_options.color.map_or(wgpu::LoadOp::Load, |color| {
                                 wgpu::LoadOp::Clear(wgpu_color(color))
@@ -692,37 +891,41 @@ impl
                     depth_stencil_attachment,
                 });
 
-                if let Some(pip) = rp.pipeline {
-                    rpass.set_pipeline(&pip.raw);
+                if let (Some(pip), Some(raw)) = (rp.pipeline, &raw_pipeline) {
+                    rpass.set_pipeline(raw);
 
                     let mut vertex_buffers_slot = 0;
                     let mut indexed = false;
-                    rp.buffers.iter().for_each(|buff| match buff.usage {
-                        BufferUsage::Vertex => {
-                            rpass.set_vertex_buffer(vertex_buffers_slot, buff.raw.slice(..));
+                    rp.buffers.iter().for_each(|rb| match rb.buffer.usage {
+                        BufferUsage::Vertex | BufferUsage::Storage => {
+                            let slice = rb.buffer.raw.slice(rb.range());
+                            rpass.set_vertex_buffer(vertex_buffers_slot, slice);
                             vertex_buffers_slot += 1;
                         }
                         BufferUsage::Index => {
                             debug_assert!(!indexed, "Cannot bind more than one Index buffer");
                             indexed = true;
-                            rpass.set_index_buffer(buff.raw.slice(..), pip.index_format)
+                            let slice = rb.buffer.raw.slice(rb.range());
+                            rpass.set_index_buffer(slice, pip.index_format)
                         }
                         BufferUsage::Uniform => {}
                     });
 
-                    rp.bind_groups.iter().enumerate().for_each(|(i, bg)| {
-                        rpass.set_bind_group(i as _, &bg.raw, &[]);
-                    });
-
                     if let Some(sr) = rp.stencil_ref {
                         rpass.set_stencil_reference(sr as _);
                     }
 
-                    rp.vertices.iter().for_each(|vertices| {
+                    rp.vertices.iter().enumerate().for_each(|(i, vertices)| {
+                        set_bind_groups(&mut rpass, rp, i);
+                        set_draw_area(&mut rpass, vertices, target_size);
                         if !vertices.range.is_empty() {
                             let instances = 0..vertices.instances.unwrap_or(1);
                             if indexed {
-                                rpass.draw_indexed(vertices.range.clone(), 0, instances);
+                                rpass.draw_indexed(
+                                    vertices.range.clone(),
+                                    vertices.base_vertex,
+                                    instances,
+                                );
                             } else {
                                 rpass.draw(vertices.range.clone(), instances);
                             }
@@ -739,6 +942,161 @@ impl
 
         Ok(())
     }
+
+    fn compute(&mut self, renderer: &Renderer) -> Result<(), String> {
+        let mut encoder = self
+            .ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Compute Encoder"),
+            });
+
+        renderer.passes.iter().for_each(|pass| {
+            if let Pass::Compute(cp) = pass {
+                encode_compute_pass(&mut encoder, cp);
+            }
+        });
+
+        if !renderer.passes.is_empty() {
+            self.ctx.queue.submit(Some(encoder.finish()));
+        }
+
+        Ok(())
+    }
+}
+
+/// All the bind groups are set before the first draw, then only the ones with new dynamic offsets
+fn set_bind_groups<'a>(rpass: &mut wgpu::RenderPass<'a>, rp: &'a RenderPass, draw: usize) {
+    let changes = rp.offset_changes_at(draw);
+    rp.bind_groups.iter().enumerate().for_each(|(group, bg)| {
+        let change = changes.iter().rev().find(|change| change.group == group);
+        if draw == 0 || change.is_some() {
+            let offsets = change.map_or(&[][..], |change| &rp.offsets[change.range.clone()]);
+            rpass.set_bind_group(group as _, &bg.raw, offsets);
+        }
+    });
+}
+
+/// Viewport and scissor are reset to the whole target if the draw does not set them
+fn set_draw_area<'a>(
+    rpass: &mut wgpu::RenderPass<'a>,
+    vertices: &RPassVertices,
+    (width, height): (u32, u32),
+) {
+    let vp = vertices.viewport.unwrap_or(Viewport {
+        x: 0.0,
+        y: 0.0,
+        width: width as _,
+        height: height as _,
+        min_depth: 0.0,
+        max_depth: 1.0,
+    });
+    rpass.set_viewport(vp.x, vp.y, vp.width, vp.height, vp.min_depth, vp.max_depth);
+
+    // wgpu panics if the scissor goes beyond the target
+    let rect = vertices.scissor.unwrap_or(TextureRect {
+        x: 0,
+        y: 0,
+        width,
+        height,
+    });
+    let x = rect.x.min(width);
+    let y = rect.y.min(height);
+    let w = rect.width.min(width - x);
+    let h = rect.height.min(height - y);
+    rpass.set_scissor_rect(x, y, w, h);
+}
+
+fn encode_compute_pass(encoder: &mut wgpu::CommandEncoder, cp: &ComputePass) {
+    let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor { label: None });
+
+    if let Some(pip) = cp.pipeline {
+        cpass.set_pipeline(&pip.raw);
+
+        cp.bind_groups.iter().enumerate().for_each(|(i, bg)| {
+            cpass.set_bind_group(i as _, &bg.raw, &[]);
+        });
+
+        cp.dispatches.iter().for_each(|&(x, y, z)| {
+            cpass.dispatch_workgroups(x, y, z);
+        });
+    }
+}
+
+fn create_bind_group_layouts(
+    device: &wgpu::Device,
+    label: Option<&str>,
+    layouts: &[BindGroupLayout],
+) -> Vec<wgpu::BindGroupLayout> {
+    layouts
+        .iter()
+        .map(|bgl| {
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label,
+                entries: &bgl
+                    .entries
+                    .iter()
+                    .map(|entry| {
+                        let visibility = wgpu_shader_visibility(
+                            entry.visible_vertex,
+                            entry.visible_fragment,
+                            entry.visible_compute,
+                        );
+                        let ty = match entry.typ {
+                            BindType::Texture => wgpu::BindingType::Texture {
+                                multisampled: false,
+                                view_dimension: wgpu::TextureViewDimension::D2,
+                                sample_type: wgpu::TextureSampleType::Float {
+                                    filterable: entry.filterable,
+                                },
+                            },
+                            BindType::Sampler => {
+                                wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering)
+                            }
+                            BindType::Uniform => wgpu::BindingType::Buffer {
+                                ty: wgpu::BufferBindingType::Uniform,
+                                has_dynamic_offset: entry.dynamic_offset,
+                                min_binding_size: None,
+                            },
+                            BindType::StorageBuffer { read_only } => wgpu::BindingType::Buffer {
+                                ty: wgpu::BufferBindingType::Storage { read_only },
+                                has_dynamic_offset: false,
+                                min_binding_size: None,
+                            },
+                            BindType::StorageTexture(format) => wgpu::BindingType::StorageTexture {
+                                access: wgpu::StorageTextureAccess::WriteOnly,
+                                format: wgpu_texture_format(format),
+                                view_dimension: wgpu::TextureViewDimension::D2,
+                            },
+                        };
+
+                        wgpu::BindGroupLayoutEntry {
+                            binding: entry.location,
+                            visibility,
+                            ty,
+                            count: None,
+                        }
+                    })
+                    .collect::<Vec<_>>(),
+            })
+        })
+        .collect()
+}
+
+fn bind_group_layout_refs(
+    next_resource_id: &mut u64,
+    descriptors: &[BindGroupLayout],
+    layouts: Vec<wgpu::BindGroupLayout>,
+) -> ArrayVec<BindGroupLayoutRef, MAX_BIND_GROUPS_PER_PIPELINE> {
+    descriptors
+        .iter()
+        .zip(layouts)
+        .map(|(desc, bgl)| BindGroupLayoutRef {
+            id: resource_id(next_resource_id),
+            dynamic_uniforms: desc.dynamic_uniforms().into(),
+            raw: Arc::new(bgl),
+        })
+        .collect()
 }
 
 fn resource_id<T: From<u64>>(count: &mut u64) -> T {
@@ -752,6 +1110,7 @@ fn create_texture(
     queue: &Queue,
     desc: TextureDescriptor,
     data: Option<TextureData>,
+    samples: u32,
     id: TextureId,
 ) -> Result<Texture, String> {
     let size = data.map_or(wgpu::Extent3d::default(), |d| wgpu::Extent3d {
@@ -760,45 +1119,65 @@ fn create_texture(
         depth_or_array_layers: 1,
     });
 
-    let is_depth_texture = match desc.format {
-        TextureFormat::Depth32Float => true,
-        _ => false,
-    };
-    let mut usage = wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST;
-    if is_depth_texture || desc.write {
+    let format = wgpu_texture_format(desc.format);
+    if !device.features().contains(format.required_features()) {
+        return Err(format!(
+            "Texture format '{:?}' is not supported by the adapter",
+            desc.format
+        ));
+    }
+
+    let is_depth_texture = desc.format.is_depth();
+    let mut usage = wgpu::TextureUsages::TEXTURE_BINDING
+        | wgpu::TextureUsages::COPY_DST
+        | wgpu::TextureUsages::COPY_SRC;
+    let is_renderable = !desc.format.is_compressed();
+    if is_renderable && (is_depth_texture || desc.write || desc.mipmaps) {
         usage |= wgpu::TextureUsages::RENDER_ATTACHMENT;
     }
+    if desc.storage {
+        usage |= wgpu::TextureUsages::STORAGE_BINDING;
+    }
+
+    // multisampled textures are only used as attachments
+    if samples > 1 {
+        usage = wgpu::TextureUsages::RENDER_ATTACHMENT;
+    }
 
+    let mip_levels = data.map_or(1, |d| d.mip_levels(&desc));
     let raw = device.create_texture(&wgpu::TextureDescriptor {
         label: desc.label,
         size,
-        mip_level_count: 1,
-        sample_count: 1,
+        mip_level_count: mip_levels,
+        sample_count: samples,
         dimension: TextureDimension::D2,
-        format: wgpu_texture_format(desc.format),
+        format,
         usage,
         view_formats: &[],
     });
 
     if !is_depth_texture {
         if let Some(d) = data {
-            if !d.bytes.is_empty() {
-                queue.write_texture(
-                    wgpu::ImageCopyTexture {
-                        texture: &raw,
-                        mip_level: 0,
-                        origin: wgpu::Origin3d::ZERO,
-                        aspect: wgpu::TextureAspect::All,
-                    },
-                    d.bytes,
-                    wgpu::ImageDataLayout {
-                        offset: 0,
-                        bytes_per_row: Some(d.width * 4),
-                        rows_per_image: Some(d.height),
-                    },
-                    size,
-                );
-            }
+            let levels = std::iter::once(d.bytes).chain(d.mips.iter().copied());
+            levels
+                .enumerate()
+                .filter(|(_, bytes)| !bytes.is_empty())
+                .for_each(|(level, bytes)| {
+                    let (width, height) = mip_level_size(d.width, d.height, level as _);
+                    write_texture_data(
+                        queue,
+                        &raw,
+                        desc.format,
+                        level as _,
+                        TextureRect {
+                            x: 0,
+                            y: 0,
+                            width,
+                            height,
+                        },
+                        bytes,
+                    );
+                });
         }
     }
 
@@ -809,15 +1188,152 @@ fn create_texture(
         raw: Arc::new(raw),
         view: Arc::new(view),
         size: (size.width, size.height),
+        format: desc.format,
         write: desc.write,
+        mip_levels,
+        mipmaps: desc.mipmaps,
     })
 }
 
+fn write_texture_data(
+    queue: &Queue,
+    texture: &wgpu::Texture,
+    format: TextureFormat,
+    mip_level: u32,
+    rect: TextureRect,
+    data: &[u8],
+) {
+    // compressed formats are copied by whole blocks even if the mip level is smaller
+    let (block_width, block_height) = format.block_dimensions();
+    let rows = rect.height.div_ceil(block_height);
+    queue.write_texture(
+        wgpu::ImageCopyTexture {
+            texture,
+            mip_level,
+            origin: wgpu::Origin3d {
+                x: rect.x,
+                y: rect.y,
+                z: 0,
+            },
+            aspect: wgpu::TextureAspect::All,
+        },
+        data,
+        wgpu::ImageDataLayout {
+            offset: 0,
+            bytes_per_row: format.bytes_per_row(rect.width),
+            rows_per_image: Some(rows),
+        },
+        wgpu::Extent3d {
+            width: rect.width.div_ceil(block_width) * block_width,
+            height: rows * block_height,
+            depth_or_array_layers: 1,
+        },
+    );
+}
+
+fn read_texture_pixels(
+    ctx: &Context,
+    mut encoder: wgpu::CommandEncoder,
+    texture: &wgpu::Texture,
+    format: wgpu::TextureFormat,
+    size: (u32, u32),
+    rect: TextureRect,
+) -> Result<Vec<u8>, String> {
+    let TextureRect {
+        x,
+        y,
+        width,
+        height,
+    } = rect;
+    let out_of_bounds = x.checked_add(width).is_none_or(|right| right > size.0)
+        || y.checked_add(height).is_none_or(|bottom| bottom > size.1);
+    if out_of_bounds {
+        return Err(format!(
+            "Invalid rect '{:?}' to read pixels from texture with size '{:?}'",
+            rect, size
+        ));
+    }
+
+    let unpadded_bytes_per_row = width * 4;
+    let padded_bytes_per_row = padded_bytes_per_row(unpadded_bytes_per_row);
+
+    let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Read Pixels Buffer"),
+        size: padded_bytes_per_row as u64 * height as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    if width != 0 && height != 0 {
+        encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d { x, y, z: 0 },
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+
+    ctx.queue.submit(Some(encoder.finish()));
+
+    if width == 0 || height == 0 {
+        return Ok(vec![]);
+    }
+
+    let slice = buffer.slice(..);
+    let (tx, rx) = std::sync::mpsc::channel();
+    slice.map_async(wgpu::MapMode::Read, move |res| {
+        let _ = tx.send(res);
+    });
+
+    // Wait for the GPU to finish the copy, the callback is called during the poll
+    ctx.device.poll(wgpu::Maintain::Wait);
+    rx.try_recv()
+        .map_err(|_| "Cannot map the buffer to read the texture's pixels".to_string())?
+        .map_err(|e| e.to_string())?;
+
+    let mut bytes = {
+        let data = slice.get_mapped_range();
+        remove_row_padding(
+            &data,
+            unpadded_bytes_per_row as _,
+            padded_bytes_per_row as _,
+        )
+    };
+    buffer.unmap();
+
+    // always return the pixels as RGBA
+    let is_bgra = matches!(
+        format,
+        wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb
+    );
+    if is_bgra {
+        bytes.chunks_exact_mut(4).for_each(|px| px.swap(0, 2));
+    }
+
+    Ok(bytes)
+}
+
 fn add_depth_texture_to(
     device: &wgpu::Device,
     queue: &Queue,
     surface: &mut Surface,
     format: TextureFormat,
+    samples: u32,
     label: Option<&str>,
     id: TextureId,
 ) -> Result<(), String> {
@@ -828,12 +1344,16 @@ fn add_depth_texture_to(
             label,
             format,
             write: true,
+            storage: false,
+            mipmaps: false,
         },
         Some(TextureData {
             bytes: &[],
             width: surface.config.width,
             height: surface.config.height,
+            mips: &[],
         }),
+        samples,
         id,
     )?;
 
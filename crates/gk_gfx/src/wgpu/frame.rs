@@ -18,7 +18,11 @@ pub struct DrawFrame {
     pub(crate) present_check: FramePresented,
 }
 
-impl GKDrawFrame for DrawFrame {}
+impl GKDrawFrame for DrawFrame {
+    fn window_id(&self) -> WindowId {
+        self.window_id
+    }
+}
 
 impl Debug for DrawFrame {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
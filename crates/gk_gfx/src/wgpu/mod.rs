@@ -4,6 +4,7 @@ mod bind_group;
 mod buffer;
 mod device;
 mod frame;
+mod mipmaps;
 mod pipeline;
 mod render_texture;
 mod sampler;
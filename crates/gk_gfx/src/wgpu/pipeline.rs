@@ -1,13 +1,14 @@
 use crate::consts::MAX_BIND_GROUPS_PER_PIPELINE;
-use crate::{BindGroupLayoutId, BindGroupLayoutRef, GKRenderPipeline, PipelineId};
+use crate::{BindGroupLayoutRef, GKComputePipeline, GKRenderPipeline, IndexFormat, PipelineId};
 use arrayvec::ArrayVec;
-use std::sync::Arc;
-use wgpu::RenderPipeline as RawRenderPipeline;
+use hashbrown::HashMap;
+use std::sync::{Arc, Mutex};
+use wgpu::{ComputePipeline as RawComputePipeline, RenderPipeline as RawRenderPipeline};
 
 #[derive(Clone)]
 pub struct RenderPipeline {
     pub(crate) id: PipelineId,
-    pub(crate) raw: Arc<RawRenderPipeline>,
+    pub(crate) source: Arc<RenderPipelineSource>,
     pub(crate) index_format: wgpu::IndexFormat,
     pub(crate) uses_depth: bool,
     pub(crate) uses_stencil: bool,
@@ -19,6 +20,100 @@ impl GKRenderPipeline for RenderPipeline {
         self.id
     }
 
+    fn index_format(&self) -> IndexFormat {
+        match self.index_format {
+            wgpu::IndexFormat::Uint16 => IndexFormat::UInt16,
+            wgpu::IndexFormat::Uint32 => IndexFormat::UInt32,
+        }
+    }
+
+    fn bind_group_layout_id(&self, index: u32) -> Result<&BindGroupLayoutRef, String> {
+        self.bind_group_layout
+            .get(index as usize)
+            .ok_or_else(|| format!("Invalid Bind Group '{}' in pipeline", index))
+    }
+}
+
+impl RenderPipeline {
+    /// Raw pipeline for targets using `samples`, it's created the first time it's needed
+    pub(crate) fn raw(&self, device: &wgpu::Device, samples: u32) -> Arc<RawRenderPipeline> {
+        let mut pipelines = self.source.pipelines.lock().unwrap();
+        pipelines
+            .entry(samples)
+            .or_insert_with(|| Arc::new(self.source.create(device, samples)))
+            .clone()
+    }
+}
+
+/// Vertex buffer layout owned by the pipeline
+pub(crate) struct VertexBufferSource {
+    pub array_stride: wgpu::BufferAddress,
+    pub step_mode: wgpu::VertexStepMode,
+    pub attributes: Vec<wgpu::VertexAttribute>,
+}
+
+/// The state needed to create the raw pipeline for each sample count
+pub(crate) struct RenderPipelineSource {
+    pub label: Option<String>,
+    pub shader: wgpu::ShaderModule,
+    pub layout: wgpu::PipelineLayout,
+    pub vs_entry: String,
+    pub fs_entry: String,
+    pub buffers: Vec<VertexBufferSource>,
+    pub color_target: wgpu::ColorTargetState,
+    pub primitive: wgpu::PrimitiveState,
+    pub depth_stencil: Option<wgpu::DepthStencilState>,
+    pub pipelines: Mutex<HashMap<u32, Arc<RawRenderPipeline>>>,
+}
+
+impl RenderPipelineSource {
+    fn create(&self, device: &wgpu::Device, samples: u32) -> RawRenderPipeline {
+        let buffers = self
+            .buffers
+            .iter()
+            .map(|buffer| wgpu::VertexBufferLayout {
+                array_stride: buffer.array_stride,
+                step_mode: buffer.step_mode,
+                attributes: &buffer.attributes,
+            })
+            .collect::<Vec<_>>();
+
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: self.label.as_deref(),
+            layout: Some(&self.layout),
+            vertex: wgpu::VertexState {
+                module: &self.shader,
+                entry_point: &self.vs_entry,
+                buffers: &buffers,
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &self.shader,
+                entry_point: &self.fs_entry,
+                targets: &[Some(self.color_target.clone())],
+            }),
+            primitive: self.primitive,
+            depth_stencil: self.depth_stencil.clone(),
+            multisample: wgpu::MultisampleState {
+                count: samples,
+                ..Default::default()
+            },
+            multiview: None,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct ComputePipeline {
+    pub(crate) id: PipelineId,
+    pub(crate) raw: Arc<RawComputePipeline>,
+    pub(crate) bind_group_layout: ArrayVec<BindGroupLayoutRef, MAX_BIND_GROUPS_PER_PIPELINE>,
+}
+
+impl GKComputePipeline for ComputePipeline {
+    fn id(&self) -> PipelineId {
+        self.id
+    }
+
     fn bind_group_layout_id(&self, index: u32) -> Result<&BindGroupLayoutRef, String> {
         self.bind_group_layout
             .get(index as usize)
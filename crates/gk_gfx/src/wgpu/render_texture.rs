@@ -1,14 +1,18 @@
 use crate::frame::GKDrawFrame;
 use crate::render_target::RenderTarget;
 use crate::render_texture::{GKRenderTexture, RenderTextureId};
+use crate::wgpu::texture::MsaaTexture;
 use crate::Texture;
 use std::ops::Deref;
+use std::sync::Arc;
 
 #[derive(Clone, Debug)]
 pub struct RenderTexture {
     pub(crate) id: RenderTextureId,
     pub(crate) texture: Texture,
     pub(crate) depth_texture: Option<Texture>,
+    pub(crate) msaa_texture: Option<Arc<MsaaTexture>>,
+    pub(crate) samples: u32,
 }
 
 impl GKRenderTexture for RenderTexture {
@@ -24,6 +28,10 @@ impl GKRenderTexture for RenderTexture {
         let Self { texture, .. } = self;
         texture
     }
+
+    fn samples(&self) -> u32 {
+        self.samples
+    }
 }
 
 impl Deref for RenderTexture {
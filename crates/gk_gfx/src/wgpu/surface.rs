@@ -1,4 +1,5 @@
 use super::context::Context;
+use super::texture::MsaaTexture;
 use crate::attrs::GfxAttributes;
 use crate::Texture;
 use gk_sys::window::GKWindow;
@@ -13,6 +14,7 @@ pub(crate) struct Surface {
     pub config: SurfaceConfiguration,
     pub capabilities: Arc<SurfaceCapabilities>,
     pub depth_texture: Texture,
+    pub msaa_texture: Option<Arc<MsaaTexture>>,
 }
 
 impl Surface {
@@ -35,8 +37,15 @@ impl Surface {
 
         let (width, height) = window.physical_size();
         let capabilities = surface.get_capabilities(&ctx.adapter);
+
+        // allow to read the pixels from the frame if the surface supports it
+        let mut usage = wgpu::TextureUsages::RENDER_ATTACHMENT;
+        if capabilities.usages.contains(wgpu::TextureUsages::COPY_SRC) {
+            usage |= wgpu::TextureUsages::COPY_SRC;
+        }
+
         let config = SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            usage,
             format: capabilities.formats[0],
             width,
             height,
@@ -61,9 +70,18 @@ impl Surface {
             config,
             capabilities: Arc::new(capabilities),
             depth_texture,
+            msaa_texture: None,
         })
     }
 
+    /// Creates the multisampled color texture with the surface's size if `samples` is above 1
+    pub fn update_msaa(&mut self, device: &Device, samples: u32) {
+        self.msaa_texture = (samples > 1).then(|| {
+            let size = (self.config.width, self.config.height);
+            Arc::new(MsaaTexture::new(device, self.config.format, size, samples))
+        });
+    }
+
     pub fn resize(&mut self, device: &Device, width: u32, height: u32) {
         self.config.width = width;
         self.config.height = height;
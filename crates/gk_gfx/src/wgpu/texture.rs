@@ -1,6 +1,6 @@
 use crate::frame::GKDrawFrame;
 use crate::render_target::RenderTarget;
-use crate::texture::{GKTexture, TextureId};
+use crate::texture::{GKTexture, TextureFormat, TextureId};
 use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
 use wgpu::{Texture as RawTexture, TextureView};
@@ -11,7 +11,11 @@ pub struct Texture {
     pub(crate) raw: Arc<RawTexture>,
     pub(crate) view: Arc<TextureView>,
     pub(crate) size: (u32, u32),
+    pub(crate) format: TextureFormat,
     pub(crate) write: bool,
+    pub(crate) mip_levels: u32,
+    /// The mip levels are generated from the first one
+    pub(crate) mipmaps: bool,
 }
 
 impl GKTexture for Texture {
@@ -30,6 +34,18 @@ impl GKTexture for Texture {
     fn height(&self) -> u32 {
         self.size.1
     }
+
+    fn mip_levels(&self) -> u32 {
+        self.mip_levels
+    }
+
+    fn format(&self) -> TextureFormat {
+        self.format
+    }
+
+    fn is_writable(&self) -> bool {
+        self.write
+    }
 }
 
 impl Debug for Texture {
@@ -40,3 +56,42 @@ impl Debug for Texture {
             .finish()
     }
 }
+
+/// Multisampled color attachment that is resolved into the render target
+#[derive(Debug)]
+pub(crate) struct MsaaTexture {
+    _raw: RawTexture,
+    pub view: TextureView,
+    pub samples: u32,
+}
+
+impl MsaaTexture {
+    pub fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        size: (u32, u32),
+        samples: u32,
+    ) -> Self {
+        let raw = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("MSAA Texture"),
+            size: wgpu::Extent3d {
+                width: size.0,
+                height: size.1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: samples,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let view = raw.create_view(&wgpu::TextureViewDescriptor::default());
+
+        Self {
+            _raw: raw,
+            view,
+            samples,
+        }
+    }
+}
@@ -1,5 +1,4 @@
 use crate::color::Color;
-use crate::consts::SURFACE_DEFAULT_DEPTH_FORMAT;
 use crate::{
     BlendComponent, BlendFactor, BlendMode, BlendOperation, BufferUsage, ColorMask, CompareMode,
     CullMode, DepthStencil, IndexFormat, Primitive, Stencil, StencilAction, TextureFilter,
@@ -21,6 +20,9 @@ pub fn wgpu_buffer_usages(usage: BufferUsage) -> wgpu::BufferUsages {
         BufferUsage::Vertex => BufferUsages::VERTEX,
         BufferUsage::Index => BufferUsages::INDEX,
         BufferUsage::Uniform => BufferUsages::UNIFORM,
+        BufferUsage::Storage => {
+            BufferUsages::STORAGE | BufferUsages::VERTEX | BufferUsages::COPY_SRC
+        }
     }
 }
 
@@ -80,8 +82,33 @@ pub fn wgpu_texture_format(format: TextureFormat) -> wgpu::TextureFormat {
     match format {
         TextureFormat::Rgba8UnormSrgb => wgpu::TextureFormat::Rgba8UnormSrgb,
         TextureFormat::Bgra8UnormSrgb => wgpu::TextureFormat::Bgra8UnormSrgb,
+        TextureFormat::Rgba8Unorm => wgpu::TextureFormat::Rgba8Unorm,
+        TextureFormat::R8Unorm => wgpu::TextureFormat::R8Unorm,
+        TextureFormat::Rgba16Float => wgpu::TextureFormat::Rgba16Float,
+        TextureFormat::Rgba32Float => wgpu::TextureFormat::Rgba32Float,
         // TextureFormat::Depth16 => wgpu::TextureFormat::Depth16Unorm,
-        TextureFormat::Depth32Float => wgpu::TextureFormat::Depth24PlusStencil8,
+        TextureFormat::Depth32Float => wgpu::TextureFormat::Depth32Float,
+        TextureFormat::Depth24PlusStencil8 => wgpu::TextureFormat::Depth24PlusStencil8,
+        TextureFormat::Bc1RgbaUnorm => wgpu::TextureFormat::Bc1RgbaUnorm,
+        TextureFormat::Bc1RgbaUnormSrgb => wgpu::TextureFormat::Bc1RgbaUnormSrgb,
+        TextureFormat::Bc3RgbaUnorm => wgpu::TextureFormat::Bc3RgbaUnorm,
+        TextureFormat::Bc3RgbaUnormSrgb => wgpu::TextureFormat::Bc3RgbaUnormSrgb,
+        TextureFormat::Bc4RUnorm => wgpu::TextureFormat::Bc4RUnorm,
+        TextureFormat::Bc5RgUnorm => wgpu::TextureFormat::Bc5RgUnorm,
+        TextureFormat::Bc7RgbaUnorm => wgpu::TextureFormat::Bc7RgbaUnorm,
+        TextureFormat::Bc7RgbaUnormSrgb => wgpu::TextureFormat::Bc7RgbaUnormSrgb,
+        TextureFormat::Etc2Rgb8Unorm => wgpu::TextureFormat::Etc2Rgb8Unorm,
+        TextureFormat::Etc2Rgb8UnormSrgb => wgpu::TextureFormat::Etc2Rgb8UnormSrgb,
+        TextureFormat::Etc2Rgba8Unorm => wgpu::TextureFormat::Etc2Rgba8Unorm,
+        TextureFormat::Etc2Rgba8UnormSrgb => wgpu::TextureFormat::Etc2Rgba8UnormSrgb,
+        TextureFormat::Astc4x4Unorm => wgpu::TextureFormat::Astc {
+            block: wgpu::AstcBlock::B4x4,
+            channel: wgpu::AstcChannel::Unorm,
+        },
+        TextureFormat::Astc4x4UnormSrgb => wgpu::TextureFormat::Astc {
+            block: wgpu::AstcBlock::B4x4,
+            channel: wgpu::AstcChannel::UnormSrgb,
+        },
     }
 }
 
@@ -185,6 +212,7 @@ pub fn wgpu_compare_mode(mode: CompareMode) -> wgpu::CompareFunction {
 }
 
 pub fn wgpu_depth_stencil(
+    format: TextureFormat,
     depth: Option<DepthStencil>,
     stencil: Option<Stencil>,
 ) -> Option<wgpu::DepthStencilState> {
@@ -198,7 +226,7 @@ pub fn wgpu_depth_stencil(
     };
 
     Some(wgpu::DepthStencilState {
-        format: wgpu_texture_format(SURFACE_DEFAULT_DEPTH_FORMAT),
+        format: wgpu_texture_format(format),
         depth_write_enabled,
         depth_compare,
         stencil: stencil.map_or(Default::default(), |stencil| {
@@ -253,3 +281,48 @@ pub fn wgpu_write_mask(mask: ColorMask) -> wgpu::ColorWrites {
 
     raw_mask
 }
+
+/// Bytes per row aligned to what wgpu needs to copy textures to buffers
+pub fn padded_bytes_per_row(unpadded_bytes_per_row: u32) -> u32 {
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    let padding = (align - unpadded_bytes_per_row % align) % align;
+    unpadded_bytes_per_row + padding
+}
+
+/// Removes the padding added at the end of each row
+pub fn remove_row_padding(
+    data: &[u8],
+    unpadded_bytes_per_row: usize,
+    padded_bytes_per_row: usize,
+) -> Vec<u8> {
+    let rows = data.len() / padded_bytes_per_row;
+    let mut bytes = Vec::with_capacity(unpadded_bytes_per_row * rows);
+    data.chunks(padded_bytes_per_row)
+        .for_each(|row| bytes.extend_from_slice(&row[..unpadded_bytes_per_row]));
+    bytes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_padded_bytes_per_row() {
+        assert_eq!(padded_bytes_per_row(4), 256);
+        assert_eq!(padded_bytes_per_row(256), 256);
+        assert_eq!(padded_bytes_per_row(260), 512);
+    }
+
+    #[test]
+    fn test_remove_row_padding() {
+        let padded = padded_bytes_per_row(8) as usize;
+        let mut data = vec![0; padded * 2];
+        data[..8].copy_from_slice(&[1; 8]);
+        data[padded..padded + 8].copy_from_slice(&[2; 8]);
+
+        let bytes = remove_row_padding(&data, 8, padded);
+        assert_eq!(bytes.len(), 16);
+        assert_eq!(&bytes[..8], &[1; 8]);
+        assert_eq!(&bytes[8..], &[2; 8]);
+    }
+}
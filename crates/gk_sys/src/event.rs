@@ -60,6 +60,13 @@ pub struct FrameStartEvent;
 #[derive(Debug, Copy, Clone)]
 pub struct UpdateEvent;
 
+/// Dispatched 0..N times per frame, before `UpdateEvent`, using a fixed time step
+#[derive(Debug, Copy, Clone)]
+pub struct FixedUpdateEvent {
+    /// Fixed step in seconds
+    pub delta: f32,
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct DrawEvent {
     pub window_id: WindowId,
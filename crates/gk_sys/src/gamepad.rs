@@ -0,0 +1,87 @@
+#[derive(Copy, Clone, Hash, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadId(u64);
+
+impl From<u64> for GamepadId {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<GamepadId> for u64 {
+    fn from(value: GamepadId) -> Self {
+        value.0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GamepadEvent {
+    pub id: GamepadId,
+    pub action: GamepadAction,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GamepadAction {
+    /// A new gamepad was connected
+    Connected {
+        name: String,
+    },
+    /// The gamepad was disconnected
+    Disconnected,
+    ButtonPressed {
+        button: GamepadButton,
+    },
+    ButtonReleased {
+        button: GamepadButton,
+    },
+    /// Axis value normalized between -1.0 and 1.0
+    AxisChanged {
+        axis: GamepadAxis,
+        value: f32,
+    },
+}
+
+/// Buttons of a gamepad, using the layout of a common Xbox/PlayStation controller
+#[derive(Clone, Copy, Hash, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GamepadButton {
+    /// Bottom action button (A on Xbox, Cross on PlayStation)
+    South,
+    /// Right action button (B on Xbox, Circle on PlayStation)
+    East,
+    /// Top action button (Y on Xbox, Triangle on PlayStation)
+    North,
+    /// Left action button (X on Xbox, Square on PlayStation)
+    West,
+    C,
+    Z,
+    LeftTrigger,
+    LeftTrigger2,
+    RightTrigger,
+    RightTrigger2,
+    Select,
+    Start,
+    Mode,
+    LeftThumb,
+    RightThumb,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+    Unknown,
+}
+
+/// Axes of a gamepad
+#[derive(Clone, Copy, Hash, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    LeftZ,
+    RightStickX,
+    RightStickY,
+    RightZ,
+    DPadX,
+    DPadY,
+    Unknown,
+}
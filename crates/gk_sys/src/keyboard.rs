@@ -4,12 +4,54 @@ use crate::window::WindowId;
 pub struct KeyboardEvent {
     pub window_id: WindowId,
     pub action: KeyboardAction,
+    pub modifiers: KeyModifiers,
 }
 
 #[derive(Copy, Clone, Debug)]
 pub enum KeyboardAction {
     Pressed { key: KeyCode },
     Released { key: KeyCode },
+    ModifiersChanged,
+}
+
+/// State of the modifier keys when the event was sent
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyModifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    /// Command key on macOS, Windows key on Windows
+    pub logo: bool,
+}
+
+/// Text typed by the user, already processed by the platform's keyboard layout
+#[derive(Clone, Debug)]
+pub struct TextEvent {
+    pub window_id: WindowId,
+    pub text: String,
+}
+
+/// Input Method Editor's event
+#[derive(Clone, Debug)]
+pub struct ImeEvent {
+    pub window_id: WindowId,
+    pub action: ImeAction,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ImeAction {
+    /// The IME was enabled
+    Enabled,
+    /// Text being composed, with the byte-wise cursor range if visible
+    Preedit {
+        text: String,
+        cursor: Option<(usize, usize)>,
+    },
+    /// Text that must be inserted
+    Commit { text: String },
+    /// The IME was disabled
+    Disabled,
 }
 /// KeyCode represents the symbolic name of the keyboard keys pressed
 /// This enum code comes from `winit` just adding the Unknown key for non-compatible keys between platforms
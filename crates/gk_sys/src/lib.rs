@@ -5,11 +5,13 @@ mod sys;
 mod utils;
 
 pub mod event;
+pub mod gamepad;
 pub mod handlers;
 pub mod keyboard;
 pub mod mouse;
 pub mod prelude;
 pub mod storage;
+pub mod touch;
 pub mod window;
 
 pub use builder::AppBuilder;
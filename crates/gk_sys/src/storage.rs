@@ -27,7 +27,8 @@ impl Plugins {
         self.map.insert(plugin);
     }
 
-    pub(crate) fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
+    /// Allows mutable access to a plugin stored
+    pub fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
         self.map.get_mut()
     }
 }
@@ -52,6 +53,12 @@ impl<S: GKState, T: Plugin + 'static> FromStorage<S> for T {
     }
 }
 
+impl<S: GKState + 'static> FromStorage<S> for Plugins {
+    fn from_storage(storage: &mut Storage<S>) -> &mut Self {
+        &mut storage.plugins
+    }
+}
+
 impl<S: GKState + 'static> FromStorage<S> for EventQueue<S> {
     fn from_storage(storage: &mut Storage<S>) -> &mut Self {
         &mut storage.events
@@ -0,0 +1,37 @@
+use crate::window::WindowId;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TouchEvent {
+    pub window_id: WindowId,
+    /// Unique id of the finger until the touch ends
+    pub id: u64,
+    pub phase: TouchPhase,
+    pub x: f32,
+    pub y: f32,
+    /// Normalized pressure (0.0 to 1.0) if the device supports it
+    pub force: Option<f32>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TouchPhase {
+    Started,
+    Moved,
+    Ended,
+    Cancelled,
+}
+
+/// Pinch and rotate gestures, from the trackpad or from two fingers touches
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GestureEvent {
+    pub window_id: WindowId,
+    pub action: GestureAction,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GestureAction {
+    /// Scale change since the last event, positive when zooming in
+    Pinch { delta: f32, phase: TouchPhase },
+    /// Rotation in degrees since the last event, positive is counterclockwise
+    Rotate { delta: f32, phase: TouchPhase },
+}
@@ -1,4 +1,5 @@
 use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+use std::path::PathBuf;
 
 #[derive(Copy, Clone, Hash, Debug, Eq, PartialEq)]
 pub struct WindowId(u64);
@@ -139,6 +140,9 @@ pub trait GKWindow: HasRawWindowHandle + HasRawDisplayHandle {
     fn set_max_size(&mut self, width: u32, height: u32);
     fn max_size(&self) -> Option<(u32, u32)>;
     fn request_redraw(&mut self);
+    fn set_ime_allowed(&mut self, allowed: bool);
+    fn ime_allowed(&self) -> bool;
+    fn set_ime_position(&mut self, x: i32, y: i32);
 }
 
 /// Window's event
@@ -182,6 +186,28 @@ pub enum WindowAction {
     Close,
 }
 
+/// Files dragged over the window
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FileDropEvent {
+    pub id: WindowId,
+    pub action: FileDropAction,
+}
+
+/// File drag and drop event type, multiple files send one event per file
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FileDropAction {
+    /// A file is being dragged over the window
+    Hovered { path: PathBuf },
+
+    /// A file was dropped into the window
+    Dropped { path: PathBuf },
+
+    /// The files were dragged out of the window without dropping them
+    Cancelled,
+}
+
 /// Represent mouse cursor icon
 #[derive(Debug, Copy, Clone, PartialOrd, PartialEq, Eq)]
 pub enum CursorIcon {
@@ -0,0 +1,27 @@
+use gamekit::app::App;
+use gamekit::assets::{AssetLoad, AssetLoader};
+use gamekit::sys::window::{FileDropAction, FileDropEvent};
+
+fn main() -> Result<(), String> {
+    gamekit::init()
+        .add_config(AssetLoader::config().with_dropped_files(true))?
+        .add_config(App::config())?
+        .on(on_file_drop)
+        .on(on_asset_load)
+        .build()
+}
+
+fn on_file_drop(evt: &FileDropEvent) {
+    match &evt.action {
+        FileDropAction::Hovered { path } => log::info!("Hovering file {:?}", path),
+        FileDropAction::Dropped { path } => log::info!("Dropped file {:?}", path),
+        FileDropAction::Cancelled => log::info!("Drag and drop cancelled"),
+    }
+}
+
+fn on_asset_load(evt: &AssetLoad) {
+    match evt.data() {
+        Ok(buff) => log::info!("Loaded -> '{}' ({} bytes)", evt.id(), buff.len()),
+        Err(err) => log::error!("{}", err),
+    }
+}
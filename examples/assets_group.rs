@@ -0,0 +1,63 @@
+use gamekit::app::App;
+use gamekit::assets::{AssetGroupLoaded, AssetLoader, Assets};
+use gamekit::gfx::{Gfx, Texture};
+use gamekit::prelude::*;
+use gamekit::sys::event;
+
+const GROUP: &str = "level";
+
+#[derive(AppState)]
+struct State {
+    last_progress: f32,
+}
+
+impl State {
+    fn new(loader: &mut AssetLoader) -> Result<Self, String> {
+        loader.load_in::<Texture>(GROUP, &asset_path("ferris.png"));
+        loader.load_in::<Texture>(GROUP, &asset_path("bunny.png"));
+        loader.load_raw_in(GROUP, &asset_path("missing.png"));
+        Ok(State { last_progress: 0.0 })
+    }
+}
+
+fn main() -> Result<(), String> {
+    gamekit::init_with(State::new)
+        .add_config(AssetLoader::config())?
+        .add_config(App::config())?
+        .add_config(Gfx::config())?
+        .add_config(Assets::<Texture>::config())?
+        .on(on_update)
+        .on(on_group_loaded)
+        .build()
+}
+
+fn on_update(_: &event::UpdateEvent, loader: &mut AssetLoader, state: &mut State) {
+    let Some(group) = loader.group(GROUP) else {
+        return;
+    };
+
+    let progress = group.progress();
+    if progress != state.last_progress {
+        log::info!("Loading '{}': {:.0}%", GROUP, progress * 100.0);
+        state.last_progress = progress;
+    }
+}
+
+fn on_group_loaded(evt: &AssetGroupLoaded) {
+    log::info!(
+        "Group '{}' done: {} loaded, {} failed",
+        evt.name,
+        evt.loaded,
+        evt.failed
+    );
+}
+
+fn asset_path(path: &str) -> String {
+    let base = if cfg!(target_arch = "wasm32") {
+        "./assets"
+    } else {
+        "./examples/assets"
+    };
+
+    format!("{base}/{path}")
+}
@@ -5,7 +5,7 @@ use gamekit::sys::event;
 
 fn main() -> Result<(), String> {
     gamekit::init()
-        .add_config(AssetLoader::config())?
+        .add_config(AssetLoader::config().with_hot_reload(true))?
         .add_config(App::config())?
         .once(on_init)
         .on(on_asset_load)
@@ -14,8 +14,8 @@ fn main() -> Result<(), String> {
 
 fn on_init(_: &event::InitEvent, loader: &mut AssetLoader) {
     loader
-        .load(&asset_path("cube.png"))
-        .load(&asset_path("bunny.png"));
+        .load_raw(&asset_path("cube.png"))
+        .load_raw(&asset_path("bunny.png"));
 }
 
 fn on_asset_load(evt: &AssetLoad) {
@@ -24,7 +24,8 @@ fn on_asset_load(evt: &AssetLoad) {
         Ok(buff) => format!("Loaded -> '{}' ({} bytes)", id, buff.len()),
         Err(err) => err,
     };
-    log::info!("Asset load event {}: {}", evt.id(), loaded);
+    let kind = if evt.is_reload() { "reload" } else { "load" };
+    log::info!("Asset {} event {}: {}", kind, evt.id(), loaded);
 }
 
 fn asset_path(path: &str) -> String {
@@ -0,0 +1,60 @@
+use gamekit::app::App;
+use gamekit::assets::{AssetLoader, Assets, Handle, LoadState};
+use gamekit::gfx::{GKTexture, Gfx, Texture};
+use gamekit::prelude::*;
+use gamekit::sys::event;
+
+#[derive(AppState)]
+struct State {
+    texture: Handle<Texture>,
+    ready: bool,
+}
+
+impl State {
+    fn new(loader: &mut AssetLoader) -> Result<Self, String> {
+        let texture = loader.load(&asset_path("ferris.png"));
+        Ok(State {
+            texture,
+            ready: false,
+        })
+    }
+}
+
+fn main() -> Result<(), String> {
+    gamekit::init_with(State::new)
+        .add_config(AssetLoader::config())?
+        .add_config(App::config())?
+        .add_config(Gfx::config())?
+        .add_config(Assets::<Texture>::config())?
+        .on(on_update)
+        .build()
+}
+
+fn on_update(_: &event::UpdateEvent, textures: &mut Assets<Texture>, state: &mut State) {
+    if state.ready {
+        return;
+    }
+
+    match textures.state(&state.texture) {
+        LoadState::Loading => {}
+        LoadState::Loaded => {
+            let size = textures.get(&state.texture).map(|tex| tex.size());
+            log::info!("Texture {:?} loaded with size {:?}", state.texture, size);
+            state.ready = true;
+        }
+        LoadState::Failed(err) => {
+            log::error!("{}", err);
+            state.ready = true;
+        }
+    }
+}
+
+fn asset_path(path: &str) -> String {
+    let base = if cfg!(target_arch = "wasm32") {
+        "./assets"
+    } else {
+        "./examples/assets"
+    };
+
+    format!("{base}/{path}")
+}
@@ -0,0 +1,92 @@
+use gamekit::gfx::{
+    Buffer, Color, Gfx, RenderPipeline, RenderTexture, Renderer, VertexFormat, VertexLayout,
+};
+use gamekit::prelude::*;
+use gamekit::sys::event::UpdateEvent;
+
+// language=wgsl
+const SHADER: &str = r#"
+struct VertexInput {
+    @location(0) position: vec2<f32>,
+    @location(1) color: vec3<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec3<f32>,
+};
+
+@vertex
+fn vs_main(
+    model: VertexInput,
+) -> VertexOutput {
+    var out: VertexOutput;
+    out.color = model.color;
+    out.position = vec4<f32>(model.position - 0.5, 0.0, 1.0);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(in.color, 1.0);
+}
+"#;
+
+#[derive(AppState)]
+struct State {
+    pip: RenderPipeline,
+    vbo: Buffer,
+    rt: RenderTexture,
+}
+
+impl State {
+    fn new(gfx: &mut Gfx) -> Result<Self, String> {
+        let pip = gfx
+            .create_render_pipeline(SHADER)
+            .with_vertex_layout(
+                VertexLayout::new()
+                    .with_attr(0, VertexFormat::Float32x2)
+                    .with_attr(1, VertexFormat::Float32x3),
+            )
+            .build()?;
+
+        #[rustfmt::skip]
+        let vertices: &[f32] = &[
+            0.5, 1.0,   1.0, 0.0, 0.0,
+            0.0, 0.0,   0.0, 1.0, 0.0,
+            1.0, 0.0,   0.0, 0.0, 1.0,
+        ];
+
+        let vbo = gfx.create_vertex_buffer(vertices).build()?;
+
+        let rt = gfx.create_render_texture().with_size(256, 256).build()?;
+
+        Ok(State { pip, vbo, rt })
+    }
+}
+
+fn main() -> Result<(), String> {
+    // No platform config is added, so the default runner will run just one frame
+    gamekit::init_with(State::new)
+        .add_config(Gfx::config().with_headless(true))?
+        .on(on_update)
+        .build()
+}
+
+fn on_update(_: &UpdateEvent, gfx: &mut Gfx, state: &mut State) {
+    // Renderer with the render pass for the triangle
+    let mut renderer = Renderer::new();
+    renderer
+        .begin_pass()
+        .clear_color(Color::rgb(0.1, 0.2, 0.3))
+        .pipeline(&state.pip)
+        .buffers(&[&state.vbo])
+        .draw(0..3);
+
+    // Render to the texture, there is no window or frame to present
+    gfx.render(&state.rt, &renderer).unwrap();
+
+    // Read back the pixels from the GPU
+    let pixels = gfx.read_pixels(&state.rt).build().unwrap();
+    log::info!("Triangle rendered offscreen ({} bytes)", pixels.len());
+}
@@ -0,0 +1,32 @@
+use gamekit::app::{App, Keyboard, Mouse};
+use gamekit::sys::event::UpdateEvent;
+use gamekit::sys::keyboard::KeyCode;
+use gamekit::sys::mouse::MouseButton;
+
+fn main() -> Result<(), String> {
+    gamekit::init()
+        .add_config(App::config())?
+        .add_config(Keyboard::config())?
+        .add_config(Mouse::config())?
+        .on(on_update)
+        .build()
+}
+
+fn on_update(_: &UpdateEvent, keyboard: &mut Keyboard, mouse: &mut Mouse) {
+    if keyboard.was_pressed(KeyCode::Space) {
+        println!("Space pressed");
+    }
+
+    if keyboard.was_released(KeyCode::Space) {
+        println!("Space released");
+    }
+
+    if mouse.was_pressed(MouseButton::Left) {
+        println!("Left click at {:?}", mouse.position());
+    }
+
+    let (_, wheel_y) = mouse.wheel_delta();
+    if wheel_y != 0.0 {
+        println!("Wheel moved {}", wheel_y);
+    }
+}
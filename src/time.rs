@@ -1,5 +1,5 @@
 use crate::utils::RingBuffer;
-use gk_sys::{event, AppBuilder, BuildConfig, GKState, Plugin};
+use gk_sys::{event, AppBuilder, BuildConfig, EventQueue, GKState, Plugin};
 use std::time::{Duration, Instant};
 
 /// Measure Application times
@@ -13,6 +13,10 @@ pub struct Time {
     elapsed_time: f32,
     fps_cache: RingBuffer<f32, 300>,
     fps: f32,
+    fixed_step: Option<Duration>,
+    max_fixed_steps: usize,
+    accumulator: Duration,
+    alpha: f32,
 }
 
 impl Default for Time {
@@ -26,13 +30,17 @@ impl Default for Time {
             elapsed_time: 0.0,
             fps_cache: Default::default(),
             fps: 0.0,
+            fixed_step: None,
+            max_fixed_steps: 0,
+            accumulator: Duration::from_secs(0),
+            alpha: 0.0,
         }
     }
 }
 
 impl Time {
     pub fn config() -> TimeConfig {
-        TimeConfig
+        TimeConfig::default()
     }
 
     #[inline]
@@ -53,6 +61,31 @@ impl Time {
         self.fps = 1.0 / (self.fps_cache.iter().sum::<f32>() / self.fps_cache.len() as f32);
     }
 
+    /// Adds the frame's delta to the accumulator and returns how many fixed steps must run
+    #[inline]
+    pub(crate) fn accumulate(&mut self, delta: Duration) -> usize {
+        let step = match self.fixed_step {
+            Some(step) if !step.is_zero() => step,
+            _ => return 0,
+        };
+
+        self.accumulator += delta;
+
+        let mut steps = 0;
+        while self.accumulator >= step {
+            self.accumulator -= step;
+            steps += 1;
+        }
+
+        // avoid the spiral of death dropping the steps that we cannot catch up with
+        if steps > self.max_fixed_steps {
+            steps = self.max_fixed_steps;
+        }
+
+        self.alpha = self.accumulator.as_secs_f32() / step.as_secs_f32();
+        steps
+    }
+
     /// Average frames per second (calculated using the last 60 frames)
     #[inline]
     pub fn fps(&self) -> f32 {
@@ -94,16 +127,168 @@ impl Time {
     pub fn last_time(&self) -> Option<Instant> {
         self.last_time
     }
+
+    /// Fixed time step used to dispatch `FixedUpdateEvent`
+    #[inline]
+    pub fn fixed_step(&self) -> Option<Duration> {
+        self.fixed_step
+    }
+
+    /// Fixed time step in seconds
+    #[inline]
+    pub fn fixed_step_f32(&self) -> f32 {
+        self.fixed_step.map_or(0.0, |step| step.as_secs_f32())
+    }
+
+    /// Interpolation value (0.0 to 1.0) between the last fixed step and the next one
+    #[inline]
+    pub fn alpha(&self) -> f32 {
+        self.alpha
+    }
 }
 
 impl Plugin for Time {}
 
-#[derive(Debug, Default, Copy, Clone)]
-pub struct TimeConfig;
+#[derive(Debug, Copy, Clone)]
+enum FixedStep {
+    Step(Duration),
+    Rate(f32),
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct TimeConfig {
+    fixed_step: Option<FixedStep>,
+    max_fixed_steps: usize,
+}
+
+impl Default for TimeConfig {
+    fn default() -> Self {
+        Self {
+            fixed_step: None,
+            max_fixed_steps: 8,
+        }
+    }
+}
+
+impl FixedStep {
+    fn duration(self) -> Result<Duration, String> {
+        let step = match self {
+            FixedStep::Step(step) => step,
+            FixedStep::Rate(hz) => (hz.is_finite() && hz > 0.0)
+                .then(|| Duration::try_from_secs_f32(1.0 / hz).ok())
+                .flatten()
+                .ok_or_else(|| {
+                    format!("Invalid fixed rate '{}', it must be a positive number", hz)
+                })?,
+        };
+
+        if step.is_zero() {
+            return Err(format!(
+                "Invalid fixed step '{:?}', it must be greater than zero",
+                step
+            ));
+        }
+
+        Ok(step)
+    }
+}
+
+impl TimeConfig {
+    /// Dispatch `FixedUpdateEvent` each `step` of time
+    pub fn with_fixed_step(mut self, step: Duration) -> Self {
+        self.fixed_step = Some(FixedStep::Step(step));
+        self
+    }
+
+    /// Dispatch `FixedUpdateEvent` the given number of times per second
+    pub fn with_fixed_rate(mut self, hz: f32) -> Self {
+        self.fixed_step = Some(FixedStep::Rate(hz));
+        self
+    }
+
+    /// Maximum number of fixed steps per frame (8 by default)
+    pub fn with_max_fixed_steps(mut self, steps: usize) -> Self {
+        self.max_fixed_steps = steps;
+        self
+    }
+}
 
 impl<S: GKState + 'static> BuildConfig<S> for TimeConfig {
     fn apply(&mut self, builder: AppBuilder<S>) -> Result<AppBuilder<S>, String> {
-        let builder = builder.on(|_: &event::FrameStartEvent, time: &mut Time| time.update());
-        Ok(builder.add_plugin(Time::default()))
+        let fixed_step = self.fixed_step.map(FixedStep::duration).transpose()?;
+        let builder = builder.on(
+            |_: &event::FrameStartEvent, time: &mut Time, events: &mut EventQueue<S>| {
+                time.update();
+
+                // fixed updates are queued to run after FrameStart and before Update
+                let steps = time.accumulate(time.delta);
+                let delta = time.fixed_step_f32();
+                (0..steps).for_each(|_| events.queue(event::FixedUpdateEvent { delta }));
+            },
+        );
+
+        let time = Time {
+            fixed_step,
+            max_fixed_steps: self.max_fixed_steps,
+            ..Default::default()
+        };
+        Ok(builder.add_plugin(time))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fixed_steps() {
+        let mut time = Time {
+            fixed_step: Some(Duration::from_millis(10)),
+            max_fixed_steps: 8,
+            ..Default::default()
+        };
+
+        assert_eq!(time.accumulate(Duration::from_millis(5)), 0);
+        assert_eq!(time.accumulate(Duration::from_millis(10)), 1);
+        assert!((time.alpha() - 0.5).abs() < 0.001);
+        assert_eq!(time.accumulate(Duration::from_millis(25)), 3);
+        assert!(time.alpha() < 0.001);
+
+        // steps are clamped to the max per frame
+        assert_eq!(time.accumulate(Duration::from_millis(200)), 8);
+    }
+
+    #[test]
+    fn test_fixed_rate() {
+        assert_eq!(
+            FixedStep::Rate(50.0).duration(),
+            Ok(Duration::from_millis(20))
+        );
+        assert_eq!(
+            FixedStep::Step(Duration::from_millis(5)).duration(),
+            Ok(Duration::from_millis(5))
+        );
+    }
+
+    #[test]
+    fn test_invalid_fixed_rate() {
+        assert_eq!(
+            FixedStep::Rate(0.0).duration(),
+            Err("Invalid fixed rate '0', it must be a positive number".to_string())
+        );
+        assert!(FixedStep::Rate(f32::NAN).duration().is_err());
+        assert!(FixedStep::Rate(f32::INFINITY).duration().is_err());
+        // the step doesn't fit in a Duration
+        assert!(FixedStep::Rate(1e-30).duration().is_err());
+        // the step is too small to be represented
+        assert!(FixedStep::Rate(1e30).duration().is_err());
+        assert!(FixedStep::Step(Duration::ZERO).duration().is_err());
+    }
+
+    #[test]
+    fn test_no_fixed_step() {
+        let mut time = Time::default();
+        assert_eq!(time.accumulate(Duration::from_secs(1)), 0);
+        assert_eq!(time.alpha(), 0.0);
     }
 }